@@ -0,0 +1,42 @@
+// art.rs
+//
+// The ASCII art GameConfig's fancy_output prints after each round.
+//
+
+use colored::*;  // for coloring printed output
+use crate::console::Console;
+
+
+// A cheering guppy for a win
+pub const WIN_ART: &str = r"
+   \o/   ><(((*>   \o/
+    |   YOU WIN!    |
+   / \             / \
+";
+
+// A sad face for a loss
+pub const LOSS_ART: &str = r"
+    .-----.
+   ( x   x )
+   (   ^   )   better luck next time...
+   (  ---  )
+    '-----'
+";
+
+
+/// Prints the art for how a round went.  It is colored like the round's message, so it
+/// loses its color along with everything else when colors are off.
+///
+/// # Arguments
+///
+/// * `console` - The console to print the art to.
+/// * `won` - Whether the round was a win.
+///
+pub fn print_round_art(console: &mut Console, won: bool) {
+    if won {
+        console.print(&WIN_ART.bright_green().to_string());
+    }
+    else {
+        console.print(&LOSS_ART.red().to_string());
+    }
+}
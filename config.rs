@@ -0,0 +1,270 @@
+// config.rs
+//
+// Loading a game of Guppies from a JSON config file instead of the interactive menus.
+//
+
+use std::fmt;  // for describing config errors
+use std::fs;  // for reading the config file
+use std::io;  // for the errors reading the file can have
+use std::path::{Path, PathBuf};  // for pointing at the config and log files
+use std::time::{Duration, TryFromFloatSecsError};  // for the time limit
+use serde::Deserialize;  // for reading the config file's fields
+use crate::currency::{CryptoCoin, Currency, Dollar, Euro, Hbuck, TurkishLira};
+use crate::game::{GameConfig, LossPolicy, RoundingMode};
+use crate::lang::Lang;
+use crate::variants::{
+    AlphabetGuppies, ChainGuppies, ColorDistanceGuppies, ComparatorGuppies, DateBasedGuppies, DiceGuppies,
+    FibonacciGuppies, FusionGuppies, GuppiesVariant, Manyguppies, ModularArithmeticGuppies, OddOrEvenGuppies,
+    PlainGuppies, PrimeGuppies, ProbabilityGuppies, RainbowGuppies, RainbowNumberGuppies, RomanNumeralGuppies,
+    RunningTotalGuppies, SquareRootGuppies, SumGuppies,
+};
+
+
+/// ConfigFile is what a config file holds, e.g.
+/// { "currency": "dollar", "variant": "plain", "starting_balance": 250, "max_bet": 50 }
+///
+/// # Fields
+///
+/// * 'currency' is the name of the currency: dollar, turkish_lira, hbuck, euro, or crypto_coin
+/// * 'variant' is the name of the variant: plain, rainbow, odd_or_even, many, chain, date_based,
+///   prime, modular, fibonacci, sum, roman, comparator, square_root, color_distance,
+///   alphabet, probability, fusion, running_total, dice, or rainbow_number
+/// * 'starting_balance' replaces the currency's starting amount, and can be left out
+/// * 'max_bet' caps every bet, and can be left out
+/// * 'min_bet' is the smallest bet allowed, and can be left out to keep it at 1
+/// * 'log_path' is a file to record every round to, and can be left out
+/// * 'time_limit_secs' is how many seconds each round has to be guessed in, and can be left out
+/// * 'fancy_output' prints ASCII art after every win and loss when true, and can be left out
+/// * 'loss_policy' is "full", "half", or {"fixed": amount}, and can be left out to lose the whole bet
+/// * 'loan_offer' offers a one-time loan to a player who goes broke when true, and can be left out
+/// * 'bet_rounds' is how many rounds each bet stays in play for, and can be left out to settle every round
+/// * 'win_message' and 'loss_message' replace "You were right!" and "You were incorrect.", and can be left out
+/// * 'result_path' is a file to write the game's result to as JSON, and can be left out
+/// * 'interest_rate' is the fraction of the balance earned after every round bet at the min bet,
+///   e.g. 0.05 for 5%, and can be left out for no interest
+/// * 'rounding' is "nearest", "floor", or "ceil", how fractional winnings and interest are
+///   rounded, and can be left out to round down
+/// * 'rounding_decimals' is how many decimal places they are rounded to, and can be left out
+///   for the currency's smallest unit
+/// * 'cash_out_target' ends the game once the balance reaches it, and can be left out to play on
+/// * 'confirm_bets' asks for every bet to be confirmed when true, and can be left out
+/// * 'quiet' leaves the prompts out of the output when true, and can be left out
+/// * 'rainbow_tolerance' is how many places apart on the rainbow two colors can be and still
+///   count as the same in RainbowGuppies (and ManyGuppies and FusionGuppies), and can be left out to only count the same color
+/// * 'lang' is the language of the game's messages, "en" or "es", and can be left out for English
+#[derive(Deserialize)]
+struct ConfigFile {
+    currency: String,
+    variant: String,
+    starting_balance: Option<i32>,
+    max_bet: Option<i32>,
+    min_bet: Option<i32>,
+    log_path: Option<PathBuf>,
+    time_limit_secs: Option<f64>,
+    #[serde(default)]
+    fancy_output: bool,
+    loss_policy: Option<LossPolicy>,
+    #[serde(default)]
+    loan_offer: bool,
+    bet_rounds: Option<u32>,
+    win_message: Option<String>,
+    loss_message: Option<String>,
+    result_path: Option<PathBuf>,
+    #[serde(default)]
+    interest_rate: f64,
+    rounding: Option<RoundingMode>,
+    rounding_decimals: Option<u32>,
+    cash_out_target: Option<i32>,
+    #[serde(default)]
+    confirm_bets: bool,
+    #[serde(default)]
+    quiet: bool,
+    #[serde(default)]
+    rainbow_tolerance: usize,
+    #[serde(default)]
+    lang: Lang,
+}
+
+
+/// ConfigError is everything that can go wrong loading a config file
+///
+/// # Variants
+///
+/// * 'Io' is when the file couldn't be read
+/// * 'Json' is when the file isn't valid JSON or is missing a field
+/// * 'UnknownCurrency' is when the currency name isn't one of the currencies
+/// * 'UnknownVariant' is when the variant name isn't one of the variants
+/// * 'InvalidTimeLimit' is when the time limit is negative or too big
+#[derive(Debug)]
+pub enum ConfigError {
+    Io(io::Error),
+    Json(serde_json::Error),
+    UnknownCurrency(String),
+    UnknownVariant(String),
+    InvalidTimeLimit(TryFromFloatSecsError),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConfigError::Io(error) => write!(f, "couldn't read the config file: {}", error),
+            ConfigError::Json(error) => write!(f, "the config file isn't valid: {}", error),
+            ConfigError::UnknownCurrency(name) => write!(f, "'{}' isn't a currency", name),
+            ConfigError::UnknownVariant(name) => write!(f, "'{}' isn't a variant", name),
+            ConfigError::InvalidTimeLimit(error) => write!(f, "the time limit isn't valid: {}", error),
+        }
+    }
+}
+
+// The errors that wrap another one point to it, so its details aren't lost
+impl std::error::Error for ConfigError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ConfigError::Io(error) => Some(error),
+            ConfigError::Json(error) => Some(error),
+            ConfigError::InvalidTimeLimit(error) => Some(error),
+            ConfigError::UnknownCurrency(..) | ConfigError::UnknownVariant(..) => None,
+        }
+    }
+}
+
+
+// Makes the currency a config file names, with its usual starting amount
+fn currency_from_name(name: &str) -> Option<Box<dyn Currency>> {
+    let currency: Box<dyn Currency> = match name {
+        "dollar" => Box::new(Dollar::default()),
+        "turkish_lira" => Box::new(TurkishLira::default()),
+        "hbuck" => Box::new(Hbuck::default()),
+        "euro" => Box::new(Euro::default()),
+        "crypto_coin" => Box::new(CryptoCoin::default()),
+        _ => return None,
+    };
+    Some(currency)
+}
+
+// Makes the variant a config file names, the same way the variant menu does, except that
+// RainbowGuppies counts colors up to rainbow_tolerance places apart as the same
+fn variant_from_name(name: &str, rainbow_tolerance: usize) -> Option<Box<dyn GuppiesVariant>> {
+    let variant: Box<dyn GuppiesVariant> = match name {
+        "plain" => Box::new(PlainGuppies::new()),
+        "rainbow" => Box::new(RainbowGuppies::with_tolerance(rainbow_tolerance)),
+        "odd_or_even" => Box::new(OddOrEvenGuppies::new()),
+        "many" => {
+            let plain: Box<dyn GuppiesVariant> = Box::new(PlainGuppies::new());
+            let rainbow: Box<dyn GuppiesVariant> = Box::new(RainbowGuppies::with_tolerance(rainbow_tolerance));
+            let oddoreven: Box<dyn GuppiesVariant> = Box::new(OddOrEvenGuppies::new());
+            Box::new(Manyguppies::new(vec![plain, rainbow, oddoreven]))
+        }
+        "chain" => Box::new(ChainGuppies::new()),
+        "date_based" => Box::new(DateBasedGuppies::new()),
+        "prime" => Box::new(PrimeGuppies::new()),
+        "modular" => Box::new(ModularArithmeticGuppies::new()),
+        "fibonacci" => Box::new(FibonacciGuppies::new()),
+        "sum" => Box::new(SumGuppies::new()),
+        "roman" => Box::new(RomanNumeralGuppies::new()),
+        "comparator" => Box::new(ComparatorGuppies::new()),
+        "square_root" => Box::new(SquareRootGuppies::new()),
+        "color_distance" => Box::new(ColorDistanceGuppies::new()),
+        "alphabet" => Box::new(AlphabetGuppies::new()),
+        "probability" => Box::new(ProbabilityGuppies::new()),
+        "fusion" => Box::new(FusionGuppies::new(Box::new(PlainGuppies::new()), Box::new(RainbowGuppies::with_tolerance(rainbow_tolerance)))),
+        "running_total" => Box::new(RunningTotalGuppies::new()),
+        "dice" => Box::new(DiceGuppies::new()),
+        "rainbow_number" => Box::new(RainbowNumberGuppies::new()),
+        _ => return None,
+    };
+    Some(variant)
+}
+
+
+/// Reads a GameConfig from a JSON config file.
+///
+/// # Arguments
+///
+/// * `path` - The config file to read.
+///
+/// # Returns
+///
+/// * Result: the config the file describes, or what was wrong with the file.
+///
+pub fn load_config(path: &Path) -> Result<GameConfig, ConfigError> {
+    let text = fs::read_to_string(path).map_err(ConfigError::Io)?;
+    parse_config(&text)
+}
+
+/// Reads a GameConfig from the text of a JSON config file, the way load_config does.
+///
+/// # Arguments
+///
+/// * `text` - The config file's JSON.
+///
+/// # Returns
+///
+/// * Result: the config the JSON describes, or what was wrong with it.
+///
+pub fn parse_config(text: &str) -> Result<GameConfig, ConfigError> {
+    let file: ConfigFile = serde_json::from_str(text).map_err(ConfigError::Json)?;
+    let currency = currency_from_name(&file.currency).ok_or(ConfigError::UnknownCurrency(file.currency))?;
+    let variant = variant_from_name(&file.variant, file.rainbow_tolerance).ok_or(ConfigError::UnknownVariant(file.variant))?;
+
+    let mut config = GameConfig::new(currency, variant);
+    config.starting_balance_override = file.starting_balance;
+    config.max_bet = file.max_bet;
+    if let Some(min_bet) = file.min_bet {
+        config.min_bet = min_bet;
+    }
+    config.log_path = file.log_path;
+    config.fancy_output = file.fancy_output;
+    config.loss_policy = file.loss_policy.unwrap_or(LossPolicy::Full);
+    config.loan_offer = file.loan_offer;
+    if let Some(bet_rounds) = file.bet_rounds {
+        config.bet_rounds = bet_rounds;
+    }
+    config.win_message = file.win_message;
+    config.loss_message = file.loss_message;
+    config.result_path = file.result_path;
+    config.interest_rate = file.interest_rate;
+    config.rounding = file.rounding.unwrap_or(RoundingMode::Floor);
+    config.rounding_decimals = file.rounding_decimals;
+    config.cash_out_target = file.cash_out_target;
+    config.confirm_bets = file.confirm_bets;
+    config.quiet = file.quiet;
+    config.lang = file.lang;
+    config.time_limit = match file.time_limit_secs {
+        Some(secs) => Some(Duration::try_from_secs_f64(secs).map_err(ConfigError::InvalidTimeLimit)?),
+        None => None,
+    };
+    Ok(config)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_valid_config_sets_up_the_game() {
+        let config = parse_config(r#"{ "currency": "euro", "variant": "rainbow", "starting_balance": 250, "max_bet": 50 }"#).unwrap();
+        assert_eq!(config.currency.name(), "Euros");
+        assert_eq!(config.variant.name(), "RainbowGuppies");
+        assert_eq!(config.starting_balance_override, Some(250));
+        assert_eq!(config.max_bet, Some(50));
+        assert_eq!(config.min_bet, 1);
+    }
+
+    #[test]
+    fn an_unknown_variant_is_refused() {
+        let result = parse_config(r#"{ "currency": "dollar", "variant": "checkers" }"#);
+        assert!(matches!(result, Err(ConfigError::UnknownVariant(name)) if name == "checkers"));
+    }
+
+    #[test]
+    fn every_variant_name_makes_a_variant() {
+        let names = ["plain", "rainbow", "odd_or_even", "many", "chain", "date_based", "prime", "modular", "fibonacci", "sum",
+            "roman", "comparator", "square_root", "color_distance", "alphabet", "probability", "fusion", "running_total", "dice",
+            "rainbow_number"];
+        for name in names {
+            assert!(variant_from_name(name, 0).is_some(), "{}", name);
+        }
+    }
+}
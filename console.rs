@@ -0,0 +1,364 @@
+// console.rs
+//
+// Reading the user's input and writing the game's output for Guppies.
+//
+
+use std::io::{BufRead, Write};  // for reading from and writing to any console, not just stdin/stdout
+use std::num::IntErrorKind;  // for telling a number that's too big from one that isn't a number
+use std::ops::RangeInclusive;  // for the range of valid menu choices
+use std::sync::mpsc;  // for handing a line read on another thread back
+use std::thread;  // for reading a line while waiting on a deadline
+use std::time::Instant;  // for deadlines on reading input
+use colored::*;  // for coloring printed output
+use crate::error::GuppiesError;
+
+
+/// Console is where the game reads the user's input from and writes its output to.
+/// The binary hands it stdin and stdout, but any BufRead/Write pair works, so a whole game can be
+/// driven by scripted input (e.g. a Cursor over some bytes) and have its output captured.  The
+/// input has to be Send, since a read against a deadline happens on another thread.
+///
+/// # Fields
+///
+/// * 'input' is where lines of user input are read from
+/// * 'output' is where prompts and messages are written to
+/// * 'quiet' leaves the prompts out when reading input, e.g. for scripted input, while
+///   everything else is still printed
+///
+pub struct Console<'a> {
+    pub input: &'a mut (dyn BufRead + Send),
+    pub output: &'a mut dyn Write,
+    pub quiet: bool,
+}
+
+impl Console<'_> {
+    /// Writes a line of text to the console's output, the same way println! does for stdout.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - A string slice that holds the line to be written.
+    ///
+    pub fn print(&mut self, text: &str) {
+        writeln!(self.output, "{}", text).expect("Failed to write line");
+    }
+}
+
+/// Prints a given prompt and reads a line of input from the console as a String.  A quiet
+/// console reads the line without printing the prompt.
+///
+/// # Arguments
+///
+/// * `console` - The console to print the prompt to and read the line from.
+/// * `prompt` - A string slice that holds the prompt to be printed.
+///
+/// # Returns
+///
+/// * Option<String>: the line without its surrounding whitespace, or None if the input has
+///   run out (Ctrl-D, or the end of piped input), which callers treat as the user quitting.
+///
+pub fn read_input(console: &mut Console, prompt: &str) -> Option<String> {
+    if !console.quiet {
+        console.print(&prompt.yellow().to_string());
+    }
+    let mut line = String::new();  // buffer for reading input from the user
+    let bytes_read = console.input.read_line(&mut line).expect("Failed to read line");
+    if bytes_read == 0 {
+        return None;
+    }
+    let trimmed = line.trim();  // drop whitespace
+    Some(trimmed.to_string())
+}
+
+/// Prints a given prompt and reads a line of input the way read_input does, but only waits for
+/// it until deadline.  The line is read on another thread, so time_up is printed right at the
+/// deadline even while the user is still typing.  The read can't be called off, though, so
+/// this still only returns once the user finishes the line (or the input runs out), and that
+/// late line is thrown away.
+///
+/// # Arguments
+///
+/// * `console` - The console to print the prompt to and read the line from.
+/// * `prompt` - A string slice that holds the prompt to be printed.
+/// * `deadline` - When to stop waiting for the line.
+/// * `time_up` - What to print once the deadline has passed.
+///
+/// # Returns
+///
+/// * Result: the line (or None if the input has run out) like read_input, or
+///   GuppiesError::TimedOut if the deadline passed before the line came in, even though the
+///   line is waited for before returning.
+///
+pub fn read_input_by(console: &mut Console, prompt: &str, deadline: Instant, time_up: &str) -> Result<Option<String>, GuppiesError> {
+    if !console.quiet {
+        console.print(&prompt.yellow().to_string());
+    }
+    let Some(time_left) = deadline.checked_duration_since(Instant::now()) else {
+        console.print(time_up);
+        return Err(GuppiesError::TimedOut);
+    };
+    let Console { input, output, .. } = console;
+    thread::scope(|scope| {
+        let (sender, receiver) = mpsc::channel();
+        scope.spawn(move || {
+            let mut line = String::new();  // buffer for reading input from the user
+            let bytes_read = input.read_line(&mut line).expect("Failed to read line");
+            // Nobody is listening for a line that comes in after the deadline, so it's dropped
+            let _ = sender.send((bytes_read > 0).then(|| line.trim().to_string()));
+        });
+        match receiver.recv_timeout(time_left) {
+            Ok(line) => Ok(line),
+            // The scope still waits for the late line, but the user is told as soon as time is up
+            Err(_) => {
+                writeln!(output, "{}", time_up).expect("Failed to write line");
+                Err(GuppiesError::TimedOut)
+            }
+        }
+    })
+}
+
+/// Turns a guess into the single letter the variant checks, ignoring case and spaces.  A
+/// guess can be typed as its letter or spelled out as one of the variant's words, e.g. "H",
+/// "higher", and "HIGHER" are all h.  "q" (or "quit") and "?" are taken in every variant.
+///
+/// # Arguments
+///
+/// * `raw` - The guess the user typed.
+/// * `valid` - The letters of the variant's own guesses.
+/// * `words` - The words the variant's guesses can be spelled out as, with their letters.
+///
+/// # Returns
+///
+/// * Option: the guess's letter (or "q" or "?"), or None if it isn't one of the variant's
+///   letters or words.
+///
+pub fn normalize_guess(raw: &str, valid: &[char], words: &[(&str, char)]) -> Option<char> {
+    let guess = raw.trim().to_lowercase();
+    match guess.as_str() {
+        "q" | "quit" => return Some('q'),
+        "?" => return Some('?'),
+        _ => {}
+    }
+    let mut chars = guess.chars();
+    if let (Some(letter), None) = (chars.next(), chars.next()) {
+        return valid.contains(&letter).then_some(letter);
+    }
+    words.iter().find(|(word, letter)| *word == guess && valid.contains(letter)).map(|&(_, letter)| letter)
+}
+
+/// Prints a given prompt and reads a guess from the console, the way read_input does, without
+/// its surrounding whitespace.  Turning it into a letter is up to the variant (see
+/// normalize_guess).  Running out of input is the same as guessing "q", so a game quits when
+/// its input ends.  Given a balance_line, "bal" prints it and asks again, without it counting
+/// as a guess.  Given a deadline, the guess is read with read_input_by, so it has to come in
+/// before then.
+///
+/// # Arguments
+///
+/// * `console` - The console to print the prompt to and read the guess from.
+/// * `prompt` - A string slice that holds the prompt to be printed.
+/// * `balance_line` - What "bal" prints, or None if "bal" is just a guess.
+/// * `deadline` - When the guess has to be in by and what to print if it isn't, or None for
+///   no time limit.
+///
+/// # Returns
+///
+/// * Result: the guess, or GuppiesError::TimedOut if the deadline passed first.
+///
+pub fn read_guess_input(console: &mut Console, prompt: &str, balance_line: Option<&str>, deadline: Option<(Instant, &str)>) -> Result<String, GuppiesError> {
+    loop {
+        let guess = match deadline {
+            Some((deadline, time_up)) => read_input_by(console, prompt, deadline, time_up)?,
+            None => read_input(console, prompt),
+        };
+        let Some(guess) = guess else {
+            return Ok("q".to_string());
+        };
+        match balance_line {
+            Some(balance_line) if guess.eq_ignore_ascii_case("bal") => console.print(balance_line),
+            _ => return Ok(guess),
+        }
+    }
+}
+
+/// Prints a given prompt and reads an integer from the console as an i32.
+/// Prints an error and requests input again as long as the user enters something
+/// other than an integer, including numbers too big to fit in an i32.  Returns None if the
+/// input runs out.
+///
+/// # Arguments
+///
+/// * `console` - The console to print the prompt to and read the integer from.
+/// * `prompt` - A string slice that holds the prompt to be printed.
+///
+pub fn read_int_input(console: &mut Console, prompt: &str) -> Option<i32> {
+    read_amount_input(console, prompt, 0)
+}
+
+/// Prints a given prompt and reads an amount of money from the console, counted in the
+/// currency's smallest unit (so "1.5" with 4 decimal places is 15000).  Prints an error and
+/// requests input again as long as the user enters something that isn't an amount, or has
+/// more decimal places than the currency.  Returns None if the input runs out.
+///
+/// # Arguments
+///
+/// * `console` - The console to print the prompt to and read the amount from.
+/// * `prompt` - A string slice that holds the prompt to be printed.
+/// * `decimal_places` - How many digits the currency has after the decimal point.
+///
+pub fn read_amount_input(console: &mut Console, prompt: &str, decimal_places: u32) -> Option<i32> {
+    loop {
+        let line = read_input(console, prompt)?;
+        match parse_amount(&line, decimal_places) {
+            Ok(amount) => return Some(amount),
+            Err(error) => console.print(&format!("{}  Try again...", error.to_string().red())),
+        }
+    }
+}
+
+/// Reads text the user typed as an amount counted in the smallest unit, the way
+/// read_amount_input does.  With 0 decimal places it is a plain integer.  Empty text gets its
+/// own message, since it usually means Enter was pressed before typing anything.
+///
+/// # Arguments
+///
+/// * `text` - The text to read.
+/// * `decimal_places` - How many digits the currency has after the decimal point.
+///
+/// # Returns
+///
+/// * Result: the amount, or a GuppiesError saying what's wrong with the text (EmptyAmount,
+///   InvalidAmount, or AmountTooBig).
+///
+pub fn parse_amount(text: &str, decimal_places: u32) -> Result<i32, GuppiesError> {
+    // Just pressing Enter isn't a badly typed number, so it gets a message of its own
+    if text.is_empty() {
+        return Err(GuppiesError::EmptyAmount);
+    }
+    if decimal_places > 0 {
+        return parse_fixed_point(text, decimal_places).ok_or(GuppiesError::InvalidAmount { decimal_places });
+    }
+    match text.parse::<i32>() {
+        Ok(i) => Ok(i),
+        // A number with too many digits is still a number, so it gets its own message
+        Err(error) if matches!(error.kind(), IntErrorKind::PosOverflow | IntErrorKind::NegOverflow) => {
+            Err(GuppiesError::AmountTooBig)
+        }
+        Err(..) => Err(GuppiesError::InvalidAmount { decimal_places: 0 }),
+    }
+}
+
+// Reads text like "1.5" as a whole number of the smallest unit, without going through a
+// float so no precision is lost.  None if the text isn't an amount or has too many decimals.
+fn parse_fixed_point(text: &str, decimal_places: u32) -> Option<i32> {
+    let (negative, text) = match text.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, text),
+    };
+    let (whole, fraction) = text.split_once('.').unwrap_or((text, ""));
+    if whole.is_empty() && fraction.is_empty() {
+        return None;
+    }
+    let digits = |part: &str| part.chars().all(|c| c.is_ascii_digit());
+    if !digits(whole) || !digits(fraction) || fraction.len() > decimal_places as usize {
+        return None;
+    }
+    let whole: i32 = if whole.is_empty() { 0 } else { whole.parse().ok()? };
+    let fraction: i32 = format!("{:0<width$}", fraction, width = decimal_places as usize).parse().ok()?;
+    let amount = whole.checked_mul(10i32.checked_pow(decimal_places)?)?.checked_add(fraction)?;
+    Some(if negative { -amount } else { amount })
+}
+
+/// Prints a given prompt and reads a menu choice from the console.
+/// Prints an error and requests input again as long as the user enters a number
+/// that isn't one of the menu's choices.  Returns None if the input runs out.
+///
+/// # Arguments
+///
+/// * `console` - The console to print the prompt to and read the choice from.
+/// * `prompt` - A string slice that holds the prompt to be printed.
+/// * `valid_range` - The numbers of the menu's choices.
+///
+pub fn read_menu_choice(console: &mut Console, prompt: &str, valid_range: RangeInclusive<i32>) -> Option<i32> {
+    loop {
+        let choice = read_int_input(console, prompt)?;
+        if valid_range.contains(&choice) {
+            return Some(choice);
+        }
+        let message = format!("Please enter a number from {} to {}.", valid_range.start(), valid_range.end());
+        console.print(&format!("{}  Try again...", message.red()));
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+    use super::*;
+
+    #[test]
+    fn read_int_input_asks_again_until_it_gets_a_number() {
+        let mut input = Cursor::new("abc\n42\n");
+        let mut output = Vec::new();
+        let console = &mut Console { input: &mut input, output: &mut output, quiet: false };
+        assert_eq!(read_int_input(console, "Number?"), Some(42));
+        let printed = String::from_utf8(output).unwrap();
+        assert_eq!(printed.matches("Number?").count(), 2);
+        assert!(printed.contains("Try again..."));
+    }
+
+    #[test]
+    fn read_menu_choice_asks_again_until_the_choice_is_on_the_menu() {
+        let mut input = Cursor::new("9\n2\n");
+        let mut output = Vec::new();
+        let console = &mut Console { input: &mut input, output: &mut output, quiet: true };
+        assert_eq!(read_menu_choice(console, "Choice?", 1..=4), Some(2));
+        assert!(String::from_utf8(output).unwrap().contains("Please enter a number from 1 to 4."));
+    }
+
+    #[test]
+    fn read_input_is_none_once_the_input_runs_out() {
+        let mut input = std::io::empty();
+        let mut output = Vec::new();
+        let console = &mut Console { input: &mut input, output: &mut output, quiet: false };
+        assert_eq!(read_input(console, "Anything?"), None);
+        assert_eq!(read_int_input(console, "Number?"), None);
+    }
+
+    #[test]
+    fn parse_amount_refuses_numbers_past_an_i32() {
+        assert_eq!(parse_amount("2147483647", 0).unwrap(), i32::MAX);
+        assert!(matches!(parse_amount("2147483648", 0), Err(GuppiesError::AmountTooBig)));
+        assert!(matches!(parse_amount("-9999999999", 0), Err(GuppiesError::AmountTooBig)));
+        assert!(matches!(parse_amount("12.5", 0), Err(GuppiesError::InvalidAmount {decimal_places: 0})));
+        assert!(matches!(parse_amount("214748.3648", 4), Err(GuppiesError::InvalidAmount {decimal_places: 4})));
+    }
+
+    #[test]
+    fn an_empty_amount_gets_its_own_message() {
+        let mut input = Cursor::new("\nabc\n5\n");
+        let mut output = Vec::new();
+        let console = &mut Console { input: &mut input, output: &mut output, quiet: true };
+        assert_eq!(read_int_input(console, "Bet?"), Some(5));
+        let printed = String::from_utf8(output).unwrap();
+        let empty = GuppiesError::EmptyAmount.to_string();
+        let not_a_number = parse_amount("abc", 0).unwrap_err().to_string();
+        assert_eq!(empty, "Please enter a number.");
+        assert_ne!(empty, not_a_number);
+        assert!(printed.contains(&empty) && printed.contains(&not_a_number));
+        assert!(printed.find(&empty).unwrap() < printed.find(&not_a_number).unwrap());
+    }
+
+    #[test]
+    fn guesses_can_be_spelled_out() {
+        let words = [("higher", 'h'), ("lower", 'l'), ("same", 's')];
+        let valid = ['h', 'l', 's'];
+        assert_eq!(normalize_guess("HIGHER", &valid, &words), Some('h'));
+        assert_eq!(normalize_guess(" Lower ", &valid, &words), Some('l'));
+        assert_eq!(normalize_guess("q", &valid, &words), Some('q'));
+        assert_eq!(normalize_guess("Quit", &valid, &words), Some('q'));
+        assert_eq!(normalize_guess("H", &valid, &words), Some('h'));
+        assert_eq!(normalize_guess("sideways", &valid, &words), None);
+        // A word only counts if its letter is one of the variant's guesses
+        assert_eq!(normalize_guess("same", &valid[..2], &words), None);
+    }
+}
@@ -0,0 +1,339 @@
+// currency.rs
+//
+// The Currency trait and the currencies a game of Guppies can be played in.
+//
+
+use crate::console::Console;
+
+
+/// Trait that allows the caller of run_game() to pass in an object that modifies the currency
+/// 
+/// # Methods
+/// 
+/// * starting_amount is the starting amount of money they have
+/// * symbol is the short sign amounts are written with, like $
+/// * name is the currency's full name, like Dollars
+/// * print_amount prints the amount of money they have to the console
+/// * decimal_places is how many digits the currency has after the decimal point
+/// * format_amount writes an amount out with those decimal places
+///
+/// Amounts are always whole numbers of the currency's smallest unit, so a currency with
+/// decimal places is still played with i32 money: 1.5 of a currency with 4 decimal places
+/// is an amount of 15000.  That keeps bets and payouts exact, however many rounds are played.
+///
+pub trait Currency{
+    // Starting amount, just returns an i32
+    fn starting_amount(&self) -> i32;
+    // The sign written before amounts, e.g. "$"
+    fn symbol(&self) -> &str;
+    // The full name, e.g. "Dollars"
+    fn name(&self) -> &str;
+    // takes an argument amount which is an i32 and prints it to the console
+    fn print_amount(&self, console: &mut Console, amount:i32);
+    // Whole-number currencies have no decimal places
+    fn decimal_places(&self) -> u32 {
+        0
+    }
+    // Puts the decimal point back into an amount, e.g. 15000 is 1.5000 with 4 decimal places
+    fn format_amount(&self, amount: i32) -> String {
+        format_fixed_point(amount as i64, self.decimal_places())
+    }
+}
+
+
+/// Writes an amount counted in a currency's smallest unit out with its decimal point, and
+/// with commas between the thousands of its whole part, e.g. 123456789 with 4 decimal places
+/// is 12,345.6789.
+///
+/// # Arguments
+///
+/// * `amount` - The amount, in the currency's smallest unit.
+/// * `decimal_places` - How many of the amount's digits come after the decimal point.
+///
+pub fn format_fixed_point(amount: i64, decimal_places: u32) -> String {
+    let sign = if amount < 0 { "-" } else { "" };
+    // unsigned_abs, since i64::MIN has no positive i64 to flip to
+    let amount = amount.unsigned_abs();
+    if decimal_places == 0 {
+        return format!("{}{}", sign, group_thousands(amount));
+    }
+    let unit = 10u64.pow(decimal_places);
+    format!("{}{}.{:0width$}", sign, group_thousands(amount / unit), amount % unit, width = decimal_places as usize)
+}
+
+/// Writes a whole amount of money out with commas between the thousands, e.g. 100000000 is
+/// 100,000,000 and -1500 is -1,500.
+///
+/// # Arguments
+///
+/// * `amount` - The amount of money.
+///
+pub fn format_money(amount: i32) -> String {
+    format_fixed_point(amount as i64, 0)
+}
+
+// Puts a comma before every group of three digits, counting from the right
+fn group_thousands(whole: u64) -> String {
+    let digits = whole.to_string();
+    let mut grouped = String::new();
+    for (index, digit) in digits.chars().enumerate() {
+        if index > 0 && (digits.len() - index).is_multiple_of(3) {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+    grouped
+}
+
+
+// Dollar struct which implements the currency trait, start is the starting amount
+pub struct Dollar{
+    start: i32,
+}
+
+impl Dollar {
+    /// Makes Dollars with a custom starting amount.
+    pub fn new(start: i32) -> Dollar {
+        Dollar {start}
+    }
+}
+
+// The usual starting amount is 100
+impl Default for Dollar {
+    fn default() -> Dollar {
+        Dollar::new(100)
+    }
+}
+
+impl Currency for Dollar {
+    // returns the starting amount
+    fn starting_amount(&self) -> i32 {
+        self.start
+    }
+    fn symbol(&self) -> &str {
+        "$"
+    }
+    fn name(&self) -> &str {
+        "Dollars"
+    }
+    // Method prints the amout of Dollars the user has
+    fn print_amount(&self, console: &mut Console, amount:i32){
+        console.print(&format!("You currently have {}{}", self.symbol(), format_money(amount)))
+    }
+}
+
+
+// TurkishLira struct which implements the currency trait
+pub struct TurkishLira{
+    start: i32,
+}
+
+impl TurkishLira {
+    /// Makes Turkish Lira with a custom starting amount.
+    pub fn new(start: i32) -> TurkishLira {
+        TurkishLira {start}
+    }
+}
+
+// The usual starting amount is 100000000
+impl Default for TurkishLira {
+    fn default() -> TurkishLira {
+        TurkishLira::new(100000000)
+    }
+}
+
+impl Currency for TurkishLira{
+    fn starting_amount(&self) -> i32 {
+        self.start
+    }
+    fn symbol(&self) -> &str {
+        "₺"
+    }
+    fn name(&self) -> &str {
+        "Turkish Lira"
+    }
+    // Method prints the amout of TurkishLira the user has
+    fn print_amount(&self, console: &mut Console, amount:i32){
+        console.print(&format!("You currently have {}{}", self.symbol(), format_money(amount)))
+    }
+}
+
+// Hbuck struct which implements the currency trait
+pub struct Hbuck{
+    start: i32,
+}
+
+impl Hbuck {
+    /// Makes Hbucks with a custom starting amount.
+    pub fn new(start: i32) -> Hbuck {
+        Hbuck {start}
+    }
+}
+
+// The usual starting amount is 32199
+impl Default for Hbuck {
+    fn default() -> Hbuck {
+        Hbuck::new(32199)
+    }
+}
+
+impl Currency for Hbuck{
+    fn starting_amount(&self) -> i32 {
+        self.start
+    }
+    fn symbol(&self) -> &str {
+        "H"
+    }
+    fn name(&self) -> &str {
+        "Hbucks"
+    }
+    // Method prints the amout of Hbucks the user has
+    fn print_amount(&self, console: &mut Console, amount:i32) {
+        console.print(&format!("You currently have {}{}", self.symbol(), format_money(amount)))
+    }
+}
+
+// Euro struct which implements the currency trait
+pub struct Euro{
+    start: i32,
+}
+
+impl Euro {
+    /// Makes Euros with a custom starting amount.
+    pub fn new(start: i32) -> Euro {
+        Euro {start}
+    }
+}
+
+// The usual starting amount is 85
+impl Default for Euro {
+    fn default() -> Euro {
+        Euro::new(85)
+    }
+}
+
+impl Currency for Euro{
+    fn starting_amount(&self) -> i32 {
+        self.start
+    }
+    fn symbol(&self) -> &str {
+        "€"
+    }
+    fn name(&self) -> &str {
+        "Euros"
+    }
+    // Method prints the amout of Euros the user has
+    fn print_amount(&self, console: &mut Console, amount:i32) {
+        console.print(&format!("You currently have {}{}", self.symbol(), format_money(amount)))
+    }
+}
+
+// CryptoCoin struct which implements the currency trait, counted to 4 decimal places
+pub struct CryptoCoin{
+    start: i32,
+}
+
+// A CryptoCoin amount is counted in ten-thousandths of a coin
+const CRYPTO_COIN_DECIMAL_PLACES: u32 = 4;
+
+impl CryptoCoin {
+    /// Makes CryptoCoins with a custom starting amount, in ten-thousandths of a coin.
+    pub fn new(start: i32) -> CryptoCoin {
+        CryptoCoin {start}
+    }
+}
+
+// The usual starting amount is 1.0000
+impl Default for CryptoCoin {
+    fn default() -> CryptoCoin {
+        CryptoCoin::new(10000)
+    }
+}
+
+impl Currency for CryptoCoin{
+    fn starting_amount(&self) -> i32 {
+        self.start
+    }
+    fn symbol(&self) -> &str {
+        "CC"
+    }
+    fn name(&self) -> &str {
+        "CryptoCoins"
+    }
+    // Method prints the amout of CryptoCoins the user has
+    fn print_amount(&self, console: &mut Console, amount:i32) {
+        console.print(&format!("You currently have {}{}", self.symbol(), self.format_amount(amount)))
+    }
+    fn decimal_places(&self) -> u32 {
+        CRYPTO_COIN_DECIMAL_PLACES
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn euros_start_at_85() {
+        assert_eq!(Euro::default().starting_amount(), 85);
+    }
+
+    #[test]
+    fn print_amount_spells_currently_right() {
+        let currencies: Vec<Box<dyn Currency>> = vec![
+            Box::new(Dollar::default()), Box::new(TurkishLira::default()), Box::new(Hbuck::default()),
+            Box::new(Euro::default()), Box::new(CryptoCoin::default()),
+        ];
+        for currency in currencies {
+            let mut input = std::io::empty();
+            let mut output = Vec::new();
+            let console = &mut Console { input: &mut input, output: &mut output, quiet: false };
+            currency.print_amount(console, 50);
+            let printed = String::from_utf8(output).unwrap();
+            assert!(printed.contains("currently"), "{}", printed);
+            assert!(!printed.contains("currecntly"), "{}", printed);
+        }
+    }
+
+    #[test]
+    fn custom_starting_amounts_are_kept() {
+        assert_eq!(Dollar::new(250).starting_amount(), 250);
+        assert_eq!(TurkishLira::new(5000).starting_amount(), 5000);
+        assert_eq!(Hbuck::new(7).starting_amount(), 7);
+        assert_eq!(Dollar::default().starting_amount(), 100);
+    }
+
+    #[test]
+    fn money_is_grouped_in_thousands() {
+        assert_eq!(format_money(100), "100");
+        assert_eq!(format_money(1000), "1,000");
+        assert_eq!(format_money(100000000), "100,000,000");
+        assert_eq!(format_money(-1500), "-1,500");
+        assert_eq!(format_money(i32::MIN), "-2,147,483,648");
+    }
+
+    #[test]
+    fn turkish_lira_prints_with_separators() {
+        let mut input = std::io::empty();
+        let mut output = Vec::new();
+        let console = &mut Console { input: &mut input, output: &mut output, quiet: false };
+        TurkishLira::default().print_amount(console, 100000000);
+        assert!(String::from_utf8(output).unwrap().contains("100,000,000"));
+    }
+
+    #[test]
+    fn print_amount_puts_the_symbol_before_the_amount() {
+        assert_eq!(Dollar::default().symbol(), "$");
+        assert_eq!(TurkishLira::default().symbol(), "₺");
+        assert_eq!(Hbuck::default().symbol(), "H");
+        assert_eq!(Dollar::default().name(), "Dollars");
+        let mut input = std::io::empty();
+        let mut output = Vec::new();
+        let console = &mut Console { input: &mut input, output: &mut output, quiet: false };
+        Dollar::default().print_amount(console, 1500);
+        Hbuck::default().print_amount(console, 7);
+        let printed = String::from_utf8(output).unwrap();
+        assert!(printed.contains("$1,500") && printed.contains("H7"), "{}", printed);
+    }
+}
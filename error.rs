@@ -0,0 +1,133 @@
+// error.rs
+//
+// The errors the Guppies library can hand back, so a program embedding it can match on them.
+//
+
+use std::error::Error;  // for letting the errors be used like any other error
+use std::fmt;  // for describing the errors
+use std::io;  // for the errors reading and writing files can have
+use crate::config::ConfigError;
+
+
+/// GuppiesError is everything that can go wrong in the library
+///
+/// # Variants
+///
+/// * 'InvalidGuess' is when a variant is asked to check a guess it doesn't understand, with
+///   the guess
+/// * 'OutOfRange' is when a range to draw values from is empty, with its minimum and maximum
+/// * 'IoError' is when a file couldn't be read or written
+/// * 'ConfigError' is when a config file couldn't be loaded, with what was wrong with it
+/// * 'TimedOut' is when input wasn't given before its deadline
+/// * 'EmptyAmount' is when an amount was asked for and nothing was typed
+/// * 'InvalidAmount' is when typed text isn't an amount with at most decimal_places decimal
+///   places (a whole number when it is 0)
+/// * 'AmountTooBig' is when a typed whole number is too far from 0 to fit in an i32
+/// * 'PercentTooBig' is when a bet was typed as more than 100% of the money
+/// * 'InvalidPercent' is when a bet ending in % isn't a percentage, with what was typed
+/// * 'WeightCountMismatch' is when Manyguppies is given a different number of weights than
+///   variants
+/// * 'AllWeightsZero' is when none of Manyguppies' variants has a weight above 0
+#[derive(Debug)]
+pub enum GuppiesError {
+    InvalidGuess(String),
+    OutOfRange { min: i32, max: i32 },
+    IoError(io::Error),
+    ConfigError(ConfigError),
+    TimedOut,
+    EmptyAmount,
+    InvalidAmount { decimal_places: u32 },
+    AmountTooBig,
+    PercentTooBig,
+    InvalidPercent(String),
+    WeightCountMismatch { weights: usize, variants: usize },
+    AllWeightsZero,
+}
+
+impl fmt::Display for GuppiesError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GuppiesError::InvalidGuess(guess) => write!(f, "'{}' isn't a valid guess.", guess),
+            GuppiesError::OutOfRange { min, max } => {
+                write!(f, "Invalid range: the minimum ({}) must be less than the maximum ({}).", min, max)
+            }
+            GuppiesError::IoError(error) => write!(f, "{}", error),
+            GuppiesError::ConfigError(error) => write!(f, "{}", error),
+            GuppiesError::TimedOut => write!(f, "Ran out of time."),
+            GuppiesError::EmptyAmount => write!(f, "Please enter a number."),
+            GuppiesError::InvalidAmount { decimal_places: 0 } => write!(f, "That's not an integer."),
+            GuppiesError::InvalidAmount { decimal_places } => {
+                write!(f, "That's not an amount with at most {} decimal places.", decimal_places)
+            }
+            GuppiesError::AmountTooBig => write!(f, "That number is too far from 0 (the limit is {}).", i32::MAX),
+            GuppiesError::PercentTooBig => write!(f, "You can't bet more than 100% of your money."),
+            GuppiesError::InvalidPercent(text) => write!(f, "'{}' isn't a percentage.", text),
+            GuppiesError::WeightCountMismatch { weights, variants } => {
+                write!(f, "There are {} weights for {} variants.", weights, variants)
+            }
+            GuppiesError::AllWeightsZero => write!(f, "At least one variant needs a weight above 0."),
+        }
+    }
+}
+
+// The errors that wrap another one point to it, so its details aren't lost
+impl Error for GuppiesError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            GuppiesError::IoError(error) => Some(error),
+            GuppiesError::ConfigError(error) => Some(error),
+            GuppiesError::InvalidGuess(..) | GuppiesError::OutOfRange { .. } | GuppiesError::TimedOut
+                | GuppiesError::EmptyAmount | GuppiesError::InvalidAmount { .. } | GuppiesError::AmountTooBig
+                | GuppiesError::PercentTooBig | GuppiesError::InvalidPercent(..)
+                | GuppiesError::WeightCountMismatch { .. } | GuppiesError::AllWeightsZero => None,
+        }
+    }
+}
+
+impl From<io::Error> for GuppiesError {
+    fn from(error: io::Error) -> GuppiesError {
+        GuppiesError::IoError(error)
+    }
+}
+
+impl From<ConfigError> for GuppiesError {
+    fn from(error: ConfigError) -> GuppiesError {
+        GuppiesError::ConfigError(error)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_error_describes_itself() {
+        let cases = [
+            (GuppiesError::InvalidGuess("x".to_string()), "'x' isn't a valid guess."),
+            (GuppiesError::OutOfRange {min: 6, max: 2}, "Invalid range: the minimum (6) must be less than the maximum (2)."),
+            (GuppiesError::IoError(io::Error::new(io::ErrorKind::NotFound, "no such file")), "no such file"),
+            (GuppiesError::ConfigError(ConfigError::UnknownVariant("Goldfish".to_string())), "'Goldfish' isn't a variant"),
+            (GuppiesError::TimedOut, "Ran out of time."),
+            (GuppiesError::EmptyAmount, "Please enter a number."),
+            (GuppiesError::InvalidAmount {decimal_places: 0}, "That's not an integer."),
+            (GuppiesError::InvalidAmount {decimal_places: 4}, "That's not an amount with at most 4 decimal places."),
+            (GuppiesError::AmountTooBig, "That number is too far from 0 (the limit is 2147483647)."),
+            (GuppiesError::PercentTooBig, "You can't bet more than 100% of your money."),
+            (GuppiesError::InvalidPercent("lots%".to_string()), "'lots%' isn't a percentage."),
+            (GuppiesError::WeightCountMismatch {weights: 2, variants: 3}, "There are 2 weights for 3 variants."),
+            (GuppiesError::AllWeightsZero, "At least one variant needs a weight above 0."),
+        ];
+        for (error, expected) in cases {
+            assert_eq!(error.to_string(), expected);
+        }
+    }
+
+    #[test]
+    fn an_io_error_is_wrapped_with_its_source() {
+        let error = GuppiesError::from(io::Error::new(io::ErrorKind::PermissionDenied, "denied"));
+        assert!(matches!(&error, GuppiesError::IoError(inner) if inner.kind() == io::ErrorKind::PermissionDenied));
+        assert_eq!(error.source().unwrap().to_string(), "denied");
+        assert!(GuppiesError::TimedOut.source().is_none());
+    }
+}
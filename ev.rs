@@ -0,0 +1,79 @@
+// ev.rs
+//
+// Working out the expected value of each guess, for players who want to bet by the numbers.
+//
+
+use crate::currency::format_fixed_point;
+use crate::variants::GuppiesVariant;
+
+
+/// Works out what each guess wins or loses on average, once the first value is known.  A
+/// right guess wins the bet times its multiplier (see GuppiesVariant::guess_multiplier), and a wrong one loses
+/// the whole bet, so lucky rounds, streak bonuses, and loss policies aren't counted.
+///
+/// # Arguments
+///
+/// * `variant` - The variant being played, which knows the chance of each guess being right
+///   (see GuppiesVariant::guess_chances).
+/// * `first_value` - The first value, as it was shown.
+/// * `bet` - The bet, in the currency's smallest unit.
+///
+/// # Returns
+///
+/// * Vec<(char, f64)>: each guess's letter and its expected value, in the currency's smallest
+///   unit.  Empty if the variant can't say how likely its guesses are.
+///
+pub fn expected_value<V: GuppiesVariant + ?Sized>(variant: &V, first_value: i32, bet: i32) -> Vec<(char, f64)> {
+    let bet = bet as f64;
+    variant.guess_chances(first_value).into_iter().map(|(guess, chance)| {
+        let won = bet * variant.guess_multiplier(&guess.to_string());
+        (guess, chance * won - (1.0 - chance) * bet)
+    }).collect()
+}
+
+/// Puts the expected value of each guess into words, for "ev" at the guess prompt.
+///
+/// # Arguments
+///
+/// * `variant` - The variant being played.
+/// * `first_value` - The first value, as it was shown.
+/// * `bet` - The bet, in the currency's smallest unit.
+/// * `decimal_places` - The currency's decimal places, to write the amounts out with.
+///
+pub fn describe_expected_value<V: GuppiesVariant + ?Sized>(variant: &V, first_value: i32, bet: i32, decimal_places: u32) -> String {
+    let values = expected_value(variant, first_value, bet);
+    if values.is_empty() {
+        return "Expected values aren't worked out for this game.".to_string();
+    }
+    // Two more decimal places than the currency has, since an average is rarely a whole amount
+    let guesses: Vec<String> = values.iter().map(|(guess, value)| {
+        let amount = format_fixed_point((value * 100.0).round() as i64, decimal_places + 2);
+        format!("({}) {}{}", guess, if *value >= 0.0 { "+" } else { "" }, amount)
+    }).collect();
+    format!("Expected value of a bet of {}: {}.", format_fixed_point(bet as i64, decimal_places), guesses.join(", "))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::variants::{PlainGuppies, RainbowGuppies};
+    use super::*;
+
+    #[test]
+    fn plain_guppies_at_5_by_hand() {
+        // Higher is right 5 times in 10 and pays 1x, lower 4 in 10, and the same 1 in 10 at 5x
+        let values = expected_value(&PlainGuppies::new(), 5, 10);
+        let expected = [('h', 0.0), ('l', -2.0), ('s', -4.0)];
+        assert_eq!(values.len(), expected.len());
+        for ((guess, value), (expected_guess, expected_value)) in values.into_iter().zip(expected) {
+            assert_eq!(guess, expected_guess);
+            assert!((value - expected_value).abs() < 1e-9, "({}) {}", guess, value);
+        }
+    }
+
+    #[test]
+    fn the_expected_values_are_put_into_words() {
+        assert_eq!(describe_expected_value(&PlainGuppies::new(), 5, 10, 0), "Expected value of a bet of 10: (h) +0.00, (l) -2.00, (s) -4.00.");
+        assert_eq!(describe_expected_value(&RainbowGuppies::new(), 5, 10, 0), "Expected values aren't worked out for this game.");
+    }
+}
@@ -0,0 +1,2169 @@
+// game.rs
+//
+// Running a game of Guppies: taking bets, playing rounds, and settling up at the end.
+//
+
+use std::collections::VecDeque;  // for the history of the last rounds
+use std::fs;  // for writing the result file
+use std::path::PathBuf;  // for pointing at the high score, log, and result files
+use std::cell::Cell;  // for the round to note that its time ran out
+use std::time::{Duration, Instant};  // for timing guesses against the time limit
+use rand::{Rng, RngCore};  // for generating random numbers
+use colored::*;  // for coloring printed output
+use serde::{Deserialize, Serialize};  // for reading the loss policy from config files and writing results
+use crate::art::print_round_art;
+use crate::console::{Console, parse_amount, read_input};
+use crate::lang::{Lang, MsgKey, fill, msg};
+use crate::currency::{Currency, format_fixed_point};
+use crate::error::GuppiesError;
+use crate::strategy::Strategy;
+use crate::variants::{DifferentValues, GuppiesVariant, RoundContext, RoundOutcome};
+use crate::round_log::RoundLog;
+use crate::storage::{load_high_score, save_high_score};
+
+
+/// GameConfig is what a game is played with: the currency the money is counted in, the
+/// variant of Guppies being played, and the optional rules on top of them.
+/// Start from GameConfig::new() and chain the with_ methods to set only the rules you need.
+///
+/// # Fields
+///
+/// * 'currency' is a box which holds the trait Currency
+/// * 'variant' is a box which holds the trait GuppiesVariant
+/// * 'max_bet' is the biggest bet allowed in a round, or None to only cap bets at the money the user has
+/// * 'starting_balance_override' replaces the currency's starting amount when Some, e.g. for a resumed game
+/// * 'high_score_path' is the file the best final balance in each currency is kept in, or None
+///   to not track one
+/// * 'no_color' turns off the colored output, e.g. when it is going to a file instead of a terminal
+/// * 'max_rounds' ends the game after that many rounds, or None to play until broke or quitting
+/// * 'log_path' is the file every round of run_game is recorded to, or None to not keep a log
+/// * 'time_limit' is how long the user has to guess once the first value is shown, or None
+///   for no limit.  A guess that comes in too late still ends the round, but as a loss
+/// * 'fancy_output' prints ASCII art after every win and loss
+/// * 'loss_policy' is how much of the bet a wrong guess loses, the whole bet by default
+/// * 'min_bet' is the smallest bet allowed in a round, 1 by default so a round can't be free
+/// * 'loan_offer' offers a player who goes broke a one-time loan of half the currency's
+///   starting amount to keep playing.  The loan is paid back out of the final balance when
+///   the game ends
+/// * 'bet_rounds' is how many rounds each bet stays in play for, 1 by default.  With more than
+///   one, the whole bet is set aside when it is placed, each round is played for an equal share
+///   of it (with what doesn't divide evenly played in the last round), and whatever the bet has
+///   grown or shrunk to comes back once its last round is over.  A bet has to be at least
+///   min_bet for each of its rounds
+/// * 'win_message' is printed when a guess is right, before what it won, or None for the usual
+///   "You were right!"
+/// * 'loss_message' is printed when a guess is wrong, before what it lost, or None for the usual
+///   "You were incorrect."
+/// * 'result_path' is the file the GameResult is written to as JSON when run_game ends, or None
+///   to not write one
+/// * 'interest_rate' is the fraction of their balance (e.g. 0.05 for 5%) a player earns after
+///   every round they bet the min bet (or less), rounded like winnings.  0 by default
+/// * 'rounding' is how winnings and interest that come out fractional are rounded, down by default
+/// * 'rounding_decimals' is how many decimal places winnings and interest are rounded to, or
+///   None for the currency's smallest unit.  It can't be more than the currency's decimal
+///   places, and printed amounts always show all of those
+/// * 'cash_out_target' ends the game with a congratulations once the balance reaches it, or
+///   None to play until broke, quitting, or out of rounds.  It is checked before every round
+/// * 'confirm_bets' asks the user to confirm every bet before the round is played, so a
+///   mistyped bet can be taken back
+/// * 'quiet' stops the prompts from being printed (see the console's quiet), so a game played
+///   from scripted input only prints what happened in it
+/// * 'lang' is the language the game's messages are printed in, English by default.  The
+///   variants' own prompts and rules, and the messages about input that can't be read as an
+///   amount, are only in English
+pub struct GameConfig {
+    pub currency: Box<dyn Currency>,
+    pub variant: Box<dyn GuppiesVariant>,
+    pub max_bet: Option<i32>,
+    pub starting_balance_override: Option<i32>,
+    pub high_score_path: Option<PathBuf>,
+    pub no_color: bool,
+    pub max_rounds: Option<u32>,
+    pub log_path: Option<PathBuf>,
+    pub time_limit: Option<Duration>,
+    pub fancy_output: bool,
+    pub loss_policy: LossPolicy,
+    pub min_bet: i32,
+    pub loan_offer: bool,
+    pub bet_rounds: u32,
+    pub win_message: Option<String>,
+    pub loss_message: Option<String>,
+    pub result_path: Option<PathBuf>,
+    pub interest_rate: f64,
+    pub rounding: RoundingMode,
+    pub rounding_decimals: Option<u32>,
+    pub cash_out_target: Option<i32>,
+    pub confirm_bets: bool,
+    pub quiet: bool,
+    pub lang: Lang,
+}
+
+impl GameConfig {
+    /// Makes a config for the given currency and variant with none of the optional rules.
+    pub fn new(currency: Box<dyn Currency>, variant: Box<dyn GuppiesVariant>) -> GameConfig {
+        GameConfig {
+            currency,
+            variant,
+            max_bet: None,
+            starting_balance_override: None,
+            high_score_path: None,
+            no_color: false,
+            max_rounds: None,
+            log_path: None,
+            time_limit: None,
+            fancy_output: false,
+            loss_policy: LossPolicy::Full,
+            min_bet: 1,
+            loan_offer: false,
+            bet_rounds: 1,
+            win_message: None,
+            loss_message: None,
+            result_path: None,
+            interest_rate: 0.0,
+            rounding: RoundingMode::Floor,
+            rounding_decimals: None,
+            cash_out_target: None,
+            confirm_bets: false,
+            quiet: false,
+            lang: Lang::English,
+        }
+    }
+
+    /// Caps every bet at max_bet.
+    pub fn with_max_bet(mut self, max_bet: i32) -> GameConfig {
+        self.max_bet = Some(max_bet);
+        self
+    }
+
+    /// Makes every bet at least min_bet.
+    pub fn with_min_bet(mut self, min_bet: i32) -> GameConfig {
+        self.min_bet = min_bet;
+        self
+    }
+
+    /// Starts the game with this balance instead of the currency's starting amount.
+    pub fn with_starting_balance(mut self, balance: i32) -> GameConfig {
+        self.starting_balance_override = Some(balance);
+        self
+    }
+
+    /// Keeps the best final balance in the file at path.
+    pub fn with_high_score_path(mut self, path: impl Into<PathBuf>) -> GameConfig {
+        self.high_score_path = Some(path.into());
+        self
+    }
+
+    /// Plays without colored output.
+    pub fn with_no_color(mut self) -> GameConfig {
+        self.no_color = true;
+        self
+    }
+
+    /// Ends the game once max_rounds rounds have been played.
+    pub fn with_max_rounds(mut self, max_rounds: u32) -> GameConfig {
+        self.max_rounds = Some(max_rounds);
+        self
+    }
+
+    /// Records every round to a log file at path.
+    pub fn with_log_path(mut self, path: impl Into<PathBuf>) -> GameConfig {
+        self.log_path = Some(path.into());
+        self
+    }
+
+    /// Makes every round a loss unless it is guessed within time_limit.
+    pub fn with_time_limit(mut self, time_limit: Duration) -> GameConfig {
+        self.time_limit = Some(time_limit);
+        self
+    }
+
+    /// Prints ASCII art after every win and loss.
+    pub fn with_fancy_output(mut self) -> GameConfig {
+        self.fancy_output = true;
+        self
+    }
+
+    /// Loses money on a wrong guess according to loss_policy instead of losing the whole bet.
+    pub fn with_loss_policy(mut self, loss_policy: LossPolicy) -> GameConfig {
+        self.loss_policy = loss_policy;
+        self
+    }
+
+    /// Offers a one-time loan to a player who goes broke.
+    pub fn with_loan_offer(mut self) -> GameConfig {
+        self.loan_offer = true;
+        self
+    }
+
+    /// Keeps every bet in play for bet_rounds rounds before it is settled.
+    pub fn with_bet_rounds(mut self, bet_rounds: u32) -> GameConfig {
+        self.bet_rounds = bet_rounds;
+        self
+    }
+
+    /// Prints win_message instead of "You were right!" when a guess is right.
+    pub fn with_win_message(mut self, win_message: impl Into<String>) -> GameConfig {
+        self.win_message = Some(win_message.into());
+        self
+    }
+
+    /// Prints loss_message instead of "You were incorrect." when a guess is wrong.
+    pub fn with_loss_message(mut self, loss_message: impl Into<String>) -> GameConfig {
+        self.loss_message = Some(loss_message.into());
+        self
+    }
+
+    /// Writes the game's result to path as JSON once the game is over.
+    pub fn with_result_path(mut self, path: impl Into<PathBuf>) -> GameConfig {
+        self.result_path = Some(path.into());
+        self
+    }
+
+    /// Pays interest of rate (e.g. 0.05 for 5%) on the balance after every round bet at the min bet.
+    pub fn with_interest_rate(mut self, rate: f64) -> GameConfig {
+        self.interest_rate = rate;
+        self
+    }
+
+    /// Rounds fractional winnings and interest to decimals places with the given mode.
+    pub fn with_rounding(mut self, rounding: RoundingMode, decimals: u32) -> GameConfig {
+        self.rounding = rounding;
+        self.rounding_decimals = Some(decimals);
+        self
+    }
+
+    /// Ends the game once the balance reaches cash_out_target.
+    pub fn with_cash_out_target(mut self, cash_out_target: i32) -> GameConfig {
+        self.cash_out_target = Some(cash_out_target);
+        self
+    }
+
+    /// Asks the user to confirm every bet before it is played.
+    pub fn with_confirm_bets(mut self) -> GameConfig {
+        self.confirm_bets = true;
+        self
+    }
+
+    /// Leaves the prompts out of the output, e.g. for a game played from a script.
+    pub fn with_quiet(mut self) -> GameConfig {
+        self.quiet = true;
+        self
+    }
+
+    // Rounds an amount in the currency's smallest unit the way the config says, e.g. to 2
+    // decimal places of a currency counted in ten-thousandths.  Saturates at the ends of an i32.
+    fn round_amount(&self, amount: f64) -> i32 {
+        let places = self.currency.decimal_places();
+        let unit = 10f64.powi(places as i32);
+        let decimals = self.rounding_decimals.unwrap_or(places).min(places);
+        // The last round() only drops the float error from scaling back up to whole units
+        (round_money(amount / unit, decimals, self.rounding) * unit).round() as i32
+    }
+
+    /// Prints the game's messages in lang instead of English.
+    pub fn with_lang(mut self, lang: Lang) -> GameConfig {
+        self.lang = lang;
+        self
+    }
+}
+
+
+/// LossPolicy is how much money a wrong guess costs
+///
+/// # Variants
+///
+/// * 'Full' loses the whole bet
+/// * 'Half' loses half the bet, rounded down so the odd unit stays with the player
+/// * 'Fixed' loses the same amount every time, whatever the bet (but never more than the money left)
+#[derive(Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LossPolicy {
+    Full,
+    Half,
+    Fixed(i32),
+}
+
+impl LossPolicy {
+    /// Works out how much a wrong guess costs.
+    ///
+    /// # Arguments
+    ///
+    /// * `bet` - The bet that was lost.
+    /// * `money` - The money the user had going into the round.
+    ///
+    /// # Returns
+    ///
+    /// * i32: the money to take away, from 0 up to money.
+    ///
+    pub fn loss(&self, bet: i32, money: i32) -> i32 {
+        let loss = match self {
+            LossPolicy::Full => bet,
+            LossPolicy::Half => bet / 2,
+            LossPolicy::Fixed(amount) => *amount,
+        };
+        loss.clamp(0, money)
+    }
+}
+
+
+/// RoundingMode is which way an amount that comes out between two amounts of money goes
+///
+/// # Variants
+///
+/// * 'Nearest' goes to the closer one, and away from 0 when it is halfway
+/// * 'Floor' goes down
+/// * 'Ceil' goes up
+#[derive(Clone, Copy, PartialEq, Debug, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RoundingMode {
+    Nearest,
+    Floor,
+    Ceil,
+}
+
+/// Rounds an amount of money to a number of decimal places.
+///
+/// # Arguments
+///
+/// * `value` - The amount, in whole currency (e.g. 1.2345).
+/// * `decimals` - How many decimal places to keep.
+/// * `mode` - Which way to round.
+///
+/// # Returns
+///
+/// * f64: the rounded amount, e.g. 1.23 for 1.2345 to 2 places rounding down.
+///
+pub fn round_money(value: f64, decimals: u32, mode: RoundingMode) -> f64 {
+    let scale = 10f64.powi(decimals as i32);
+    let scaled = value * scale;
+    let rounded = match mode {
+        RoundingMode::Nearest => scaled.round(),
+        RoundingMode::Floor => scaled.floor(),
+        RoundingMode::Ceil => scaled.ceil(),
+    };
+    rounded / scale
+}
+
+
+/// GameResult is what run_game hands back once a game is over, so callers can see how it went
+/// without scraping the printed output
+///
+/// # Fields
+///
+/// * 'final_balance' is the money the player had when the game ended
+/// * 'rounds_played' is how many rounds were actually checked (a quit round doesn't count)
+/// * 'quit_early' is true if the player chose to quit instead of going broke
+/// * 'stats' is how the player's rounds went, round by round
+/// * 'loan_taken' is true if the player went broke and borrowed to keep playing.  The final
+///   balance is what was left after paying the loan back
+/// * 'undo_used' is true if the player undid a round, which keeps the game off the high score
+///   and the leaderboard
+#[derive(Serialize)]
+pub struct GameResult {
+    pub final_balance: i32,
+    pub rounds_played: u32,
+    pub quit_early: bool,
+    pub stats: SessionStats,
+    pub loan_taken: bool,
+    pub undo_used: bool,
+}
+
+
+/// SessionStats sums up how a player did over a whole game
+///
+/// # Fields
+///
+/// * 'wins' is how many rounds the player guessed right
+/// * 'losses' is how many rounds the player guessed wrong
+/// * 'total_wagered' is all of the player's bets added together
+/// * 'net_change' is how much the player's balance went up (or down, if negative) over the game
+/// * 'biggest_win' is the most the player won in a single round, streak bonus included
+#[derive(Clone, Serialize)]
+pub struct SessionStats {
+    pub wins: u32,
+    pub losses: u32,
+    pub total_wagered: i64,
+    pub net_change: i32,
+    pub biggest_win: i32,
+}
+
+
+/// Writes a GameResult out as JSON, e.g. for an outside scoreboard to read.  The stats are
+/// nested under "stats", the same way they are in the struct.
+///
+/// # Arguments
+///
+/// * `result` - The result to write out.
+///
+pub fn result_to_json(result: &GameResult) -> String {
+    serde_json::to_string_pretty(result).expect("a GameResult is always valid JSON")
+}
+
+
+// A streak of this many correct guesses in a row (or more) earns a bonus on each win of
+// STREAK_BONUS_SHARE of the currency's starting amount, e.g. $10 when a game of Dollars starts at $100
+const STREAK_BONUS_THRESHOLD: u32 = 3;
+const STREAK_BONUS_SHARE: f64 = 0.1;
+
+// The loan offered to a player who goes broke is LOAN_SHARE of the currency's starting
+// amount, like the streak bonus
+const LOAN_SHARE: f64 = 0.5;
+
+// A balance below this fraction of the starting balance gets a warning after the round
+const LOW_BALANCE_FRACTION: f64 = 0.2;
+
+// The multiples of the starting balance that are celebrated when the balance first reaches them
+const MILESTONES: [i32; 3] = [2, 5, 10];
+
+// The chance that a round is lucky, and (separately) the chance that it is unlucky
+const EVENT_CHANCE: f64 = 0.05;
+
+
+// How many of the last rounds "history" at the bet prompt shows
+const HISTORY_SIZE: usize = 10;
+
+// What a round that ran out of time is recorded as having guessed, in the history and the log
+const TIMED_OUT_GUESS: &str = "-";
+
+
+// What the user can answer the bet prompt with: a bet, or "u" to undo the last round
+enum BetInput {
+    Bet(i32),
+    Undo,
+}
+
+// Where a game is with its one undo: not offered yet, offered for the last round (with the
+// player's state from before it), or used up for the rest of the game
+enum Undo {
+    Unused,
+    Offered(PlayerState),
+    Used,
+}
+
+/// Asks the user for a bet until they enter one they are allowed to make.  The bet can be
+/// typed as an amount or as a percentage of their money (see parse_bet).
+/// A bet can't be negative, can't be more than the money they have, can't be less than the
+/// min bet (for each of its rounds, with bet_rounds), and can't be more than the max bet when
+/// there is one.  A user with less money than that can still bet everything they have.  A bet also can't be so big that winning it
+/// would push the money past what an i32 can hold.  Typing "history" prints the last rounds
+/// and asks again, and a bet of "all" is only made once the user confirms it (as is every bet
+/// with confirm_bets).  Returns None if the input runs out.
+///
+/// # Arguments
+///
+/// * `console` - The console to ask the user through.
+/// * `config` - The game's config, for its currency and bet limits.
+/// * `money` - The money the user currently has.
+/// * `multiplier` - The payout multiplier a win would be paid at.
+/// * `can_undo` - Whether "u" is allowed, to undo the last round instead of betting.
+/// * `history` - The user's last rounds, oldest first.
+///
+fn read_bet(console: &mut Console, config: &GameConfig, money: i32, multiplier: f64, can_undo: bool, history: &VecDeque<HistoryEntry>) -> Option<BetInput> {
+    let currency = config.currency.as_ref();
+    // A bet over several rounds needs the min bet for each of them, or its rounds would be
+    // played for less (even for nothing).  Someone with less than that can still bet it all
+    let rounds = i32::try_from(config.bet_rounds.max(1)).unwrap_or(i32::MAX);
+    let min_bet = config.min_bet.saturating_mul(rounds).min(money);
+    let lang = config.lang;
+    let try_again = msg(MsgKey::TryAgain, lang);
+    let prompt = msg(if can_undo { MsgKey::BetPromptUndo } else { MsgKey::BetPrompt }, lang);
+    loop {
+        let line = read_input(console, prompt)?;
+        if can_undo && line.to_lowercase() == "u" {
+            return Some(BetInput::Undo);
+        }
+        if line.to_lowercase() == "history" {
+            print_history(console, currency, history, lang);
+            continue;
+        }
+        // A percentage (or all of it) works in any currency, since it is worked out from the
+        // money itself
+        let all_in = line.trim().eq_ignore_ascii_case("all");
+        let parsed = if line.ends_with('%') || all_in { parse_bet(&line, money) } else { parse_amount(&line, currency.decimal_places()) };
+        let bet = match parsed {
+            Ok(bet) => bet,
+            Err(error) => {
+                console.print(&format!("{}  {}", error.to_string().red(), try_again));
+                continue;
+            }
+        };
+        match config.max_bet {
+            Some(max) if bet > max => {
+                let message = fill(msg(MsgKey::MostYouCanBet, lang), &[&currency.format_amount(max)]);
+                console.print(&format!("{}  {}", message.red(), try_again));
+            }
+            _ if bet >= 0 && bet < min_bet => {
+                let message = fill(msg(MsgKey::LeastYouCanBet, lang), &[&currency.format_amount(min_bet)]);
+                console.print(&format!("{}  {}", message.red(), try_again));
+            }
+            _ if bet < 0 || bet > money => console.print(&format!("{}  {}", msg(MsgKey::InvalidBet, lang).red(), try_again)),
+            _ if money.checked_add(winnings(config, bet, multiplier)).is_none() => {
+                let message = msg(MsgKey::BetTooBig, lang);
+                console.print(&format!("{}  {}", message.red(), try_again));
+            }
+            _ => {
+                // Betting everything is checked first, in case "all" was typed by accident
+                if all_in {
+                    let answer = read_input(console, &fill(msg(MsgKey::AllInPrompt, lang), &[&currency.format_amount(bet)]))?;
+                    if answer.to_lowercase() != msg(MsgKey::YesAnswer, lang) {
+                        continue;
+                    }
+                }
+                else if config.confirm_bets {
+                    let prompt = fill(msg(MsgKey::ConfirmBetPrompt, lang), &[&currency.format_amount(bet), &currency.format_amount(money)]);
+                    let answer = read_input(console, &prompt)?;
+                    if answer.to_lowercase() != msg(MsgKey::YesAnswer, lang) {
+                        continue;
+                    }
+                }
+                return Some(BetInput::Bet(bet));
+            }
+        }
+    }
+}
+
+
+/// Reads a bet the user typed, either as a whole number, as a percentage of their money like
+/// "50%", or as "all" of their money.  A percentage is rounded down to whole money.
+///
+/// # Arguments
+///
+/// * `input` - The text the user typed.
+/// * `current_money` - The money the user has, which a percentage is taken of.
+///
+/// # Returns
+///
+/// * Result: the bet, or a GuppiesError saying what's wrong with the input.
+///
+pub fn parse_bet(input: &str, current_money: i32) -> Result<i32, GuppiesError> {
+    if input.trim().eq_ignore_ascii_case("all") {
+        return Ok(current_money);
+    }
+    let Some(percent) = input.strip_suffix('%') else {
+        return parse_amount(input, 0);
+    };
+    match percent.trim().parse::<u32>() {
+        Ok(percent) if percent <= 100 => Ok((current_money as i64 * percent as i64 / 100) as i32),
+        Ok(..) => Err(GuppiesError::PercentTooBig),
+        Err(..) => Err(GuppiesError::InvalidPercent(input.to_string())),
+    }
+}
+
+
+// The interest a balance earns at a rate, rounded like winnings
+fn interest(config: &GameConfig, balance: i32, rate: f64) -> i32 {
+    config.round_amount(balance as f64 * rate)
+}
+
+
+// A share of the currency's starting amount, so bonuses are worth the same to a game whatever
+// its currency is counted in.  It is at least the smallest amount there is, and at most i32::MAX
+fn share_of_start(config: &GameConfig, share: f64) -> i32 {
+    let amount = (config.currency.starting_amount() as f64 * share) as i64;
+    amount.clamp(1, i32::MAX as i64) as i32
+}
+
+// What a winning bet pays, rounded the way the config says (down to whole money by default).
+// Pays i32::MAX at most, since the cast from f64 saturates instead of wrapping around.
+fn winnings(config: &GameConfig, bet: i32, multiplier: f64) -> i32 {
+    config.round_amount(bet as f64 * multiplier)
+}
+
+// Tells the user about the bigger payout for a right "s", if the variant has one.  The guess
+// is named with the variant's own word for "s" (a "(s)quare" in SquareRootGuppies)
+fn tell_same_payout(console: &mut Console, variant: &dyn GuppiesVariant, lang: Lang) {
+    if variant.min_guess_options().contains(&'s') && variant.same_multiplier() > variant.payout_multiplier() {
+        let word = variant.guess_words().into_iter().find(|(_, letter)| *letter == 's').map_or("same", |(word, _)| word);
+        let guess = format!("(s){}", &word[1..]);
+        console.print(&fill(msg(MsgKey::SamePays, lang), &[&guess, &variant.same_multiplier().to_string()]));
+    }
+}
+
+
+/// Event is something that can happen to a round before it is played
+///
+/// # Variants
+///
+/// * 'Lucky' doubles what the round wins
+/// * 'Unlucky' doubles what the round loses, though never past the money being played with
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Event {
+    Lucky,
+    Unlucky,
+}
+
+/// Rolls for an event before a round.  About one round in ten gets one, split evenly
+/// between lucky and unlucky.
+///
+/// # Arguments
+///
+/// * `rng` - The random number generator the event is drawn from.
+///
+/// # Returns
+///
+/// * Option: the round's event, or None for an ordinary round.
+///
+pub fn roll_event(rng: &mut dyn RngCore) -> Option<Event> {
+    let roll: f64 = rng.gen();
+    if roll < EVENT_CHANCE {
+        Some(Event::Lucky)
+    }
+    else if roll < EVENT_CHANCE * 2.0 {
+        Some(Event::Unlucky)
+    }
+    else {
+        None
+    }
+}
+
+
+/// Flips a coin for double or nothing on an amount that was just won.
+///
+/// # Arguments
+///
+/// * `rng` - The random number generator the coin flip is drawn from.
+/// * `amount` - The amount being risked.
+///
+/// # Returns
+///
+/// * i32: twice the amount (at most i32::MAX) on heads, 0 on tails.
+///
+pub fn double_or_nothing(rng: &mut dyn RngCore, amount: i32) -> i32 {
+    if rng.gen_bool(0.5) {
+        amount.saturating_mul(2)
+    }
+    else {
+        0
+    }
+}
+
+
+// One round in a player's history: which round it was, what was bet and guessed, whether the
+// guess was right, and the balance after it
+#[derive(Clone)]
+struct HistoryEntry {
+    round: u32,
+    bet: i32,
+    guess: String,
+    won: bool,
+    balance: i32,
+}
+
+/// Prints a player's last rounds, oldest first, for "history" at the bet prompt.
+///
+/// # Arguments
+///
+/// * `console` - The console to print the history to.
+/// * `currency` - The currency the amounts are printed in.
+/// * `history` - The rounds to print.
+/// * `lang` - The language to print in.
+///
+fn print_history(console: &mut Console, currency: &dyn Currency, history: &VecDeque<HistoryEntry>, lang: Lang) {
+    if history.is_empty() {
+        console.print(msg(MsgKey::NoHistory, lang));
+        return;
+    }
+    console.print(&msg(MsgKey::HistoryHeader, lang).bright_purple().to_string());
+    for entry in history {
+        let result = msg(if entry.won { MsgKey::HistoryWon } else { MsgKey::HistoryLost }, lang);
+        let (bet, balance) = (currency.format_amount(entry.bet), currency.format_amount(entry.balance));
+        console.print(&fill(msg(MsgKey::HistoryEntry, lang), &[&entry.round.to_string(), &bet, &entry.guess, result, &balance]));
+    }
+}
+
+
+/// PlayerState is the money side of a game in progress for one player: their balance and
+/// how their rounds have gone so far
+///
+/// # Fields
+///
+/// * 'money' is the money the player has right now
+/// * 'starting_money' is the money the player started the game with
+/// * 'streak' counts the correct guesses in a row, a wrong guess resets it
+/// * 'rounds_played' counts the rounds that were played all the way to a result
+/// * 'stats' keeps the player's wins, losses, and bets for the summary at the end
+/// * 'riding' is the bet still in play over several rounds, if there is one (see bet_rounds)
+/// * 'history' is the player's last HISTORY_SIZE rounds, oldest first
+/// * 'milestones_reached' is how many of the MILESTONES the balance has reached, since each is
+///   only celebrated once (and an undone round can reach them again)
+#[derive(Clone)]
+struct PlayerState {
+    money: i32,
+    starting_money: i32,
+    streak: u32,
+    rounds_played: u32,
+    stats: SessionStats,
+    riding: Option<RidingBet>,
+    history: VecDeque<HistoryEntry>,
+    milestones_reached: usize,
+}
+
+// A bet that stays in play over several rounds.  'stake' is the money set aside for it, which
+// each round's winnings and losses go on, 'share' is what each round is played for, with
+// 'remainder' (what didn't divide evenly) added in the last round, and 'rounds_left' counts
+// down to when the stake comes back to the player
+#[derive(Clone)]
+struct RidingBet {
+    stake: i32,
+    share: i32,
+    remainder: i32,
+    rounds_left: u32,
+}
+
+impl RidingBet {
+    // Sets a bet aside to be played over rounds rounds
+    fn new(bet: i32, rounds: u32) -> RidingBet {
+        // Divided as i64, since rounds can be bigger than an i32 can hold
+        let share = (bet as i64 / rounds as i64) as i32;
+        let remainder = (bet as i64 % rounds as i64) as i32;
+        RidingBet {stake: bet, share, remainder, rounds_left: rounds}
+    }
+
+    // What the next round is played for
+    fn round_bet(&self) -> i32 {
+        if self.rounds_left == 1 { self.share + self.remainder } else { self.share }
+    }
+}
+
+impl PlayerState {
+    // Makes the state for a player who hasn't played yet
+    fn new(money: i32) -> PlayerState {
+        let stats = SessionStats {wins: 0, losses: 0, total_wagered: 0, net_change: 0, biggest_win: 0};
+        PlayerState {money, starting_money: money, streak: 0, rounds_played: 0, stats, riding: None, history: VecDeque::new(), milestones_reached: 0}
+    }
+
+    // The player's money, counting the stake of a bet that is still riding
+    fn balance(&self) -> i32 {
+        self.money.saturating_add(self.riding.as_ref().map_or(0, |riding| riding.stake))
+    }
+
+    // Where a round's winnings go and its losses come from: a riding bet's stake, or else the money
+    fn pot(&mut self) -> &mut i32 {
+        match &mut self.riding {
+            Some(riding) => &mut riding.stake,
+            None => &mut self.money,
+        }
+    }
+
+    // Gives a riding bet's stake back to the player, and returns how much came back
+    fn settle_bet(&mut self) -> Option<i32> {
+        let riding = self.riding.take()?;
+        self.money = self.money.saturating_add(riding.stake);
+        Some(riding.stake)
+    }
+
+    // Checks whether the player has played all the rounds the game allows
+    fn out_of_rounds(&self, max_rounds: Option<u32>) -> bool {
+        max_rounds.is_some_and(|max| self.rounds_played >= max)
+    }
+}
+
+
+/// Plays one round for a player: shows their balance, takes their bet, plays the round, and
+/// awards/removes the bet as appropriate.  While a bet is riding over several rounds, no new
+/// bet is taken and the round is played for the riding bet's share instead.  A round can be
+/// lucky or unlucky (see roll_event), which doubles what it wins or loses.
+///
+/// # Arguments
+///
+/// * `console` - The console the round is played on.
+/// * `rng` - The random number generator the round's values are drawn from.
+/// * `config` - The currency, variant, and rules the game is played with.
+/// * `state` - The player whose turn it is, updated with how the round went.
+/// * `log` - The round log to record the round to, if the game keeps one.
+/// * `undo` - The game's undo.  While it is offered, undoing puts the player back to the state
+///   from before their last round instead of playing a round, and uses it up, so a game only
+///   gets one.
+///
+/// # Returns
+///
+/// * bool: True if the round was played out (or undone), False if the player quit (or the
+///   input ran out).
+///
+fn play_turn(console: &mut Console, rng: &mut dyn RngCore, config: &mut GameConfig, state: &mut PlayerState, log: Option<&mut RoundLog>, undo: &mut Undo) -> bool {
+    // prints the starting amount of money
+    config.currency.print_amount(console, state.money);
+    let lang = config.lang;
+    
+    // Get a bet from the user, unless one is still riding.  Running out of input is the same
+    // as quitting.  The guess isn't known yet, so the bet is checked against the biggest payout.
+    let multiplier = config.variant.max_multiplier();
+    let mut bet = match &state.riding {
+        Some(riding) => {
+            let stake = config.currency.format_amount(riding.stake);
+            console.print(&fill(msg(MsgKey::BetStillRiding, lang), &[&stake, &riding.rounds_left.to_string()]));
+            riding.round_bet()
+        }
+        None => match read_bet(console, config, state.money, multiplier, matches!(undo, Undo::Offered(..)), &state.history) {
+            Some(BetInput::Bet(bet)) => bet,
+            Some(BetInput::Undo) => {
+                // Only one round a game can be undone, so the undo is used up here
+                let Undo::Offered(before) = std::mem::replace(undo, Undo::Used) else {
+                    unreachable!("undo is only offered when there is a round to undo");
+                };
+                *state = before;
+                console.print(msg(MsgKey::RoundUndone, lang));
+                return true;
+            }
+            None => return false,
+        },
+    };
+    let before_round = state.clone();
+
+    // Some rounds are lucky or unlucky, which is announced before they are played
+    let event = roll_event(rng);
+    match event {
+        Some(Event::Lucky) => console.print(&msg(MsgKey::LuckyRound, lang).bright_green().to_string()),
+        Some(Event::Unlucky) => console.print(&msg(MsgKey::UnluckyRound, lang).red().to_string()),
+        None => {}
+    }
+
+    // Plays the round, then awards/removes the bet as appropriate.  The round is timed as a
+    // whole, so with a time limit every guess in it has to be in before the limit runs out
+    // (a limit too far off to count down to is no limit).  "bal" at the guess prompt shows the
+    // balance from before the round, riding bet included, and "ev" works from the part of the
+    // bet this round is played for
+    let balance = format!("{}{}", config.currency.symbol(), config.currency.format_amount(state.balance()));
+    let round_bet = if state.riding.is_none() && config.bet_rounds > 1 { RidingBet::new(bet, config.bet_rounds).round_bet() } else { bet };
+    let round = RoundContext {
+        balance_line: Some(fill(msg(MsgKey::PeekBalance, lang), &[&balance])),
+        bet: Some(round_bet),
+        decimal_places: config.currency.decimal_places(),
+        lang,
+        deadline: config.time_limit.and_then(|limit| Instant::now().checked_add(limit)),
+        timed_out: Cell::new(false),
+    };
+    let outcome = config.variant.play_round(console, rng, &round);
+    let (result, guess) = match outcome {
+        // Running out of time ends the round like quitting, but it loses the bet.  The second
+        // value is still drawn, so the round shows (and logs) what it would have been
+        RoundOutcome::Quit if round.timed_out.get() => {
+            config.variant.generate_second(rng);
+            config.variant.tell_random(console, DifferentValues::SecondGeneratedVal);
+            (false, TIMED_OUT_GUESS.to_string())
+        }
+        RoundOutcome::Quit => return false,
+        RoundOutcome::Finished { correct, guess } => (correct, guess),
+    };
+
+    // A new bet over several rounds is only set aside once its first round has been played out
+    // (so quitting that round still takes nothing), and the round is played for its first share
+    if state.riding.is_none() && config.bet_rounds > 1 {
+        state.money -= bet;
+        let riding = RidingBet::new(bet, config.bet_rounds);
+        bet = riding.round_bet();
+        state.riding = Some(riding);
+    }
+    state.rounds_played += 1;
+    state.stats.total_wagered += bet as i64;
+    if !matches!(undo, Undo::Used) {
+        *undo = Undo::Offered(before_round);
+    }
+    if result {
+        // Harder variants (and a right "same") pay more than the bet.  The multiplier is asked
+        // for again because some variants (like ChainGuppies) only know it once the round is over.
+        let mut winnings = winnings(config, bet, config.variant.guess_multiplier(&guess));
+        if event == Some(Event::Lucky) {
+            winnings = winnings.saturating_mul(2);
+        }
+        let message = config.win_message.as_deref().unwrap_or(msg(MsgKey::YouWereRight, lang));
+        console.print(&format!("{} +{}", message, config.currency.format_amount(winnings)).bright_green().to_string());
+        state.streak += 1;
+        if state.streak >= STREAK_BONUS_THRESHOLD {
+            let bonus = share_of_start(config, STREAK_BONUS_SHARE);
+            console.print(&fill(msg(MsgKey::StreakBonus, lang), &[&config.currency.format_amount(bonus)]).bright_green().to_string());
+            winnings = winnings.saturating_add(bonus);
+        }
+        // Offers to risk what was just won on a coin flip
+        let choice = read_input(console, msg(MsgKey::DoubleOrNothingPrompt, lang));
+        if choice.is_some_and(|choice| choice.to_lowercase() == msg(MsgKey::DoubleOrNothingAnswer, lang)) {
+            winnings = double_or_nothing(rng, winnings);
+            if winnings > 0 {
+                console.print(&fill(msg(MsgKey::Doubled, lang), &[&config.currency.format_amount(winnings)]).bright_green().to_string());
+            }
+            else {
+                console.print(&msg(MsgKey::NothingLeft, lang).red().to_string());
+            }
+        }
+        // The bet was checked against overflowing, but a bonus or a long chain can still
+        // go past the most money the game can count, so the balance stops there
+        let pot = state.pot();
+        *pot = match pot.checked_add(winnings) {
+            Some(money) => money,
+            None => {
+                console.print(&msg(MsgKey::BalanceMaxed, lang).yellow().to_string());
+                i32::MAX
+            }
+        };
+        state.stats.wins += 1;
+        state.stats.biggest_win = state.stats.biggest_win.max(winnings);
+    }
+    else {
+        let pot = state.pot();
+        let mut loss = config.loss_policy.loss(bet, *pot);
+        if event == Some(Event::Unlucky) {
+            loss = loss.saturating_mul(2).min(*pot);
+        }
+        let message = config.loss_message.as_deref().unwrap_or(msg(MsgKey::YouWereIncorrect, lang));
+        console.print(&format!("{} -{}", message, config.currency.format_amount(loss)).red().to_string());
+        // The loss is never more than the money, so this can't go below 0
+        *pot = pot.checked_sub(loss).expect("losses are never more than the money");
+        state.streak = 0;
+        state.stats.losses += 1;
+    }
+
+    // The last round of a riding bet settles it
+    let bet_finished = state.riding.as_mut().is_some_and(|riding| {
+        riding.rounds_left -= 1;
+        riding.rounds_left == 0
+    });
+    if bet_finished {
+        if let Some(stake) = state.settle_bet() {
+            console.print(&fill(msg(MsgKey::BetSettled, lang), &[&config.currency.format_amount(stake)]));
+        }
+    }
+    // Careful play is rewarded with interest on the balance
+    if config.interest_rate > 0.0 && bet <= config.min_bet {
+        let earned = interest(config, state.balance(), config.interest_rate);
+        if earned > 0 {
+            state.money = state.money.saturating_add(earned);
+            console.print(&fill(msg(MsgKey::InterestEarned, lang), &[&config.currency.format_amount(earned)]).bright_green().to_string());
+        }
+    }
+    // A config can start the game below 0, so the change saturates instead of overflowing
+    state.stats.net_change = state.balance().saturating_sub(state.starting_money);
+    state.history.push_back(HistoryEntry {round: state.rounds_played, bet, guess: guess.clone(), won: result, balance: state.balance()});
+    if state.history.len() > HISTORY_SIZE {
+        state.history.pop_front();
+    }
+    if config.fancy_output {
+        print_round_art(console, result);
+    }
+
+    if let Some(log) = log {
+        if let Err(error) = log.record(state.rounds_played, config.variant.as_ref(), &guess, result, state.balance()) {
+            console.print(&format!("{} {}", msg(MsgKey::CouldntWriteLog, lang).red(), error));
+        }
+    }
+    console.print(&fill(msg(MsgKey::CurrentStreak, lang), &[&state.streak.to_string()]));
+
+    // Warns the user when they are getting close to broke
+    let balance = state.balance();
+    if balance > 0 && (balance as f64) < state.starting_money as f64 * LOW_BALANCE_FRACTION {
+        console.print(&fill(msg(MsgKey::LowBalance, lang), &[&config.currency.format_amount(balance)]).yellow().to_string());
+    }
+    true
+}
+
+
+/// Prints the summary block of a player's stats at the end of a game.
+///
+/// # Arguments
+///
+/// * `console` - The console to print the summary to.
+/// * `currency` - The currency the amounts are printed in.
+/// * `stats` - The stats to sum up.
+/// * `lang` - The language the summary is printed in.
+///
+fn print_stats(console: &mut Console, currency: &dyn Currency, stats: &SessionStats, lang: Lang) {
+    let net_change = currency.format_amount(stats.net_change);
+    let sign = if stats.net_change > 0 { "+" } else { "" };
+    let total_wagered = format_fixed_point(stats.total_wagered, currency.decimal_places());
+    console.print(&msg(MsgKey::StatsHeader, lang).bright_purple().to_string());
+    console.print(&fill(msg(MsgKey::StatsWins, lang), &[&stats.wins.to_string()]));
+    console.print(&fill(msg(MsgKey::StatsLosses, lang), &[&stats.losses.to_string()]));
+    console.print(&fill(msg(MsgKey::StatsTotalWagered, lang), &[&total_wagered]));
+    console.print(&fill(msg(MsgKey::StatsNetChange, lang), &[&format!("{}{}", sign, net_change)]));
+    console.print(&fill(msg(MsgKey::StatsBiggestWin, lang), &[&currency.format_amount(stats.biggest_win)]));
+}
+
+
+/// This function runs the actual gameplay of the guppies game
+/// 
+/// # Arguments
+/// 
+/// 
+/// * 'console' is where the bets and guesses are read from and the game is printed to
+/// * 'rng' is the random number generator every value in the game is drawn from
+/// * 'config' is the currency, variant, and rules the game is played with.  It is only
+///   borrowed, so the same config can be played again
+///
+/// There is one path for every currency: money is always an i32 counted in the currency's
+/// smallest unit, and only reading bets and printing amounts look at its decimal places.
+/// Money never overflows: a bet that could win past i32::MAX is refused, and anything else
+/// that would push the balance past it (a streak bonus, a lucky round, double or nothing,
+/// interest) stops the balance at i32::MAX instead.
+///
+/// # Returns
+///
+/// * GameResult: the final balance, number of rounds played, and whether the player quit
+///
+pub fn run_game(console: &mut Console, rng: &mut dyn RngCore, config: &mut GameConfig) -> GameResult {
+    // The colored crate decides for all of its strings at once, so this covers every message
+    if config.no_color {
+        colored::control::set_override(false);
+    }
+    // The console is only quiet for the game, so it is put back the way it was at the end
+    let was_quiet = console.quiet;
+    console.quiet |= config.quiet;
+    let lang = config.lang;
+
+    // The player starts with the currency's starting amount, unless the game starts
+    // from a balance of its own
+    let mut state = PlayerState::new(config.starting_balance_override.unwrap_or_else(|| config.currency.starting_amount()));
+    let mut quit_early = false;
+    let mut undo = Undo::Unused;
+    let mut loan_taken = false;
+    let loan = share_of_start(config, LOAN_SHARE);
+
+    // The variant may have been played before, so it starts from a clean slate
+    config.variant.reset();
+
+    // Explains the rules before the first bet
+    console.print(&config.variant.describe());
+    tell_same_payout(console, config.variant.as_ref(), lang);
+
+    // A log that can't be created is reported, and the game goes on without one
+    let mut log = match &config.log_path {
+        Some(path) => match RoundLog::create(path) {
+            Ok(log) => Some(log),
+            Err(error) => {
+                console.print(&format!("{} {}", msg(MsgKey::CouldntCreateLog, lang).red(), error));
+                None
+            }
+        },
+        None => None,
+    };
+
+    loop {
+        // A riding bet can still win back money the player set aside, even with none left in hand
+        while state.balance() > 0 {
+            if let Some(target) = config.cash_out_target.filter(|&target| state.balance() >= target) {
+                console.print(&fill(msg(MsgKey::CashOutReached, lang), &[&config.currency.format_amount(target)]).bright_green().to_string());
+                break;
+            }
+            if state.out_of_rounds(config.max_rounds) {
+                console.print(msg(MsgKey::LastRound, lang));
+                break;
+            }
+            if !play_turn(console, rng, config, &mut state, log.as_mut(), &mut undo) {
+                quit_early = true;
+                break;
+            }
+            // Multiplied as i64, so a big starting balance can't overflow.  A game that starts
+            // with nothing has no multiples to reach.
+            while state.starting_money > 0 && state.milestones_reached < MILESTONES.len()
+                && state.balance() as i64 >= state.starting_money as i64 * MILESTONES[state.milestones_reached] as i64 {
+                let multiple = MILESTONES[state.milestones_reached].to_string();
+                console.print(&fill(msg(MsgKey::MilestoneReached, lang), &[&multiple, &config.currency.format_amount(state.balance())]).bright_yellow().to_string());
+                state.milestones_reached += 1;
+            }
+        }
+
+        // A player who went broke can borrow once to keep playing
+        if state.balance() > 0 || loan_taken || !config.loan_offer {
+            break;
+        }
+        let prompt = fill(msg(MsgKey::LoanPrompt, lang), &[&config.currency.format_amount(loan)]);
+        let answer = read_input(console, &prompt).unwrap_or_default().to_lowercase();
+        if answer != msg(MsgKey::YesAnswer, lang) {
+            break;
+        }
+        state.money = loan;
+        loan_taken = true;
+        // The money from before the loan is gone, so it can't be undone back to
+        if !matches!(undo, Undo::Used) {
+            undo = Undo::Unused;
+        }
+    }
+    // A bet still riding when the game ends is settled with what its stake is worth now
+    if let Some(stake) = state.settle_bet() {
+        console.print(&fill(msg(MsgKey::BetSettled, lang), &[&config.currency.format_amount(stake)]));
+    }
+
+    // The loan is paid back out of whatever is left, as much of it as there is money for
+    if loan_taken {
+        let repaid = loan.min(state.money);
+        state.money -= repaid;
+        state.stats.net_change = state.money.saturating_sub(state.starting_money);
+        console.print(&fill(msg(MsgKey::LoanRepaid, lang), &[&config.currency.format_amount(repaid), &config.currency.format_amount(loan)]));
+    }
+    let money = state.money;
+    if let Some(log) = log {
+        if let Err(error) = log.finish() {
+            console.print(&format!("{} {}", msg(MsgKey::CouldntWriteLog, lang).red(), error));
+        }
+    }
+
+    // We got here one of four ways: the user ran out of money, the user chose to quit,
+    // the round limit was reached, or the cash out target was.  Report the result in any case.
+    if money == 0 {
+        console.print(&msg(MsgKey::Broke, lang).red().to_string());
+    }
+    else {
+        console.print(&msg(MsgKey::MadeItOut, lang).bright_green().to_string());
+        console.print(&fill(msg(MsgKey::FinalBalance, lang), &[&format!("{}{}", config.currency.symbol(), config.currency.format_amount(money))]));
+    }
+    print_stats(console, config.currency.as_ref(), &state.stats, lang);
+
+    // Checks the final balance against the stored high score and replaces it if it was beaten.
+    // A broken high score file shouldn't end the game badly, so problems are only reported.
+    // A game with an undone round doesn't count
+    if matches!(undo, Undo::Used) && config.high_score_path.is_some() {
+        console.print(msg(MsgKey::UndoNotScored, lang));
+    }
+    else if let Some(path) = &config.high_score_path {
+        match load_high_score(path, config.currency.name()) {
+            Ok(high_score) if money > high_score => {
+                console.print(&msg(MsgKey::NewHighScore, lang).bright_green().to_string());
+                if let Err(error) = save_high_score(path, config.currency.name(), money) {
+                    console.print(&format!("{} {}", msg(MsgKey::CouldntSaveHighScore, lang).red(), error));
+                }
+            }
+            Ok(_) => {}
+            Err(error) => console.print(&format!("{} {}", msg(MsgKey::CouldntReadHighScore, lang).red(), error)),
+        }
+    }
+
+    let result = GameResult {
+        final_balance: money,
+        rounds_played: state.rounds_played,
+        quit_early,
+        stats: state.stats,
+        loan_taken,
+        undo_used: matches!(undo, Undo::Used),
+    };
+    if let Some(path) = &config.result_path {
+        if let Err(error) = fs::write(path, result_to_json(&result)) {
+            console.print(&format!("{} {}", msg(MsgKey::CouldntWriteResult, lang).red(), error));
+        }
+    }
+    console.quiet = was_quiet;
+    result
+}
+
+
+/// Player is someone taking part in a hot-seat game, with the money they have
+///
+/// # Fields
+///
+/// * 'name' is what the player is called when it's their turn
+/// * 'balance' is the money the player has
+pub struct Player {
+    pub name: String,
+    pub balance: i32,
+}
+
+/// MultiplayerResult is what run_multiplayer hands back once a hot-seat game is over
+///
+/// # Fields
+///
+/// * 'players' is everyone who played, with the balance they ended with
+/// * 'winner' is the name of the player with the most money, or None if there was a tie
+pub struct MultiplayerResult {
+    pub players: Vec<Player>,
+    pub winner: Option<String>,
+}
+
+/// Runs a hot-seat game where the players share the console and take turns playing rounds,
+/// each with their own balance.  A player who quits sits out the rest of the game, and the
+/// game ends as soon as someone goes broke or everyone has quit.  A round limit in the config
+/// counts each player's rounds, so everyone gets the same number of turns.
+///
+/// # Arguments
+///
+/// * 'console' is where everyone's bets and guesses are read from and the game is printed to
+/// * 'rng' is the random number generator every value in the game is drawn from
+/// * 'players' is who is playing, in turn order, with the balance each of them starts with
+/// * 'config' is the currency, variant, and rules the game is played with
+///
+/// # Returns
+///
+/// * MultiplayerResult: everyone's final balance and who won
+///
+pub fn run_multiplayer(console: &mut Console, rng: &mut dyn RngCore, players: Vec<Player>, config: &mut GameConfig) -> MultiplayerResult {
+    if config.no_color {
+        colored::control::set_override(false);
+    }
+    // The console is only quiet for the game, so it is put back the way it was at the end
+    let was_quiet = console.quiet;
+    console.quiet |= config.quiet;
+    let lang = config.lang;
+
+    config.variant.reset();
+    console.print(&config.variant.describe());
+    tell_same_payout(console, config.variant.as_ref(), lang);
+
+    let mut states: Vec<PlayerState> = players.iter().map(|player| PlayerState::new(player.balance)).collect();
+    let mut still_playing = vec![true; players.len()];
+
+    'game: while still_playing.contains(&true) {
+        for (index, player) in players.iter().enumerate() {
+            if !still_playing[index] {
+                continue;
+            }
+            if states[index].out_of_rounds(config.max_rounds) {
+                still_playing[index] = false;
+                continue;
+            }
+            console.print(&fill(msg(MsgKey::PlayersTurn, lang), &[&player.name]).bright_purple().to_string());
+            // Undoing isn't offered in a hot-seat game, where a round can't be taken back
+            // once the other player has seen it, so it's as if it were already used
+            if !play_turn(console, rng, config, &mut states[index], None, &mut Undo::Used) {
+                console.print(&fill(msg(MsgKey::PlayerIsOut, lang), &[&player.name, &config.currency.format_amount(states[index].balance())]));
+                still_playing[index] = false;
+            }
+            else if states[index].balance() <= 0 {
+                console.print(&fill(msg(MsgKey::PlayerIsBroke, lang), &[&player.name]).red().to_string());
+                break 'game;
+            }
+        }
+    }
+
+    let players: Vec<Player> = players.into_iter().zip(states)
+        .map(|(player, state)| Player {name: player.name, balance: state.balance()})
+        .collect();
+
+    // Whoever has the most money wins, unless more than one player has that much
+    let most = players.iter().map(|player| player.balance).max().unwrap_or(0);
+    let leaders: Vec<&Player> = players.iter().filter(|player| player.balance == most).collect();
+    let winner = if leaders.len() == 1 { Some(leaders[0].name.clone()) } else { None };
+
+    for player in &players {
+        console.print(&fill(msg(MsgKey::PlayerFinished, lang), &[&player.name, &config.currency.format_amount(player.balance)]));
+    }
+    match &winner {
+        Some(name) => console.print(&fill(msg(MsgKey::PlayerWins, lang), &[name]).bright_green().to_string()),
+        None => console.print(msg(MsgKey::Tie, lang)),
+    }
+
+    console.quiet = was_quiet;
+    MultiplayerResult {players, winner}
+}
+
+
+// The guess every simulated round makes, and how many rounds a simulated game lasts when the
+// config has no round limit of its own
+const SIM_GUESS: &str = "h";
+const SIM_ROUNDS: u32 = 100;
+
+/// SimReport is what simulate hands back: how the simulated games went, all together
+///
+/// # Fields
+///
+/// * 'games' is how many games were simulated
+/// * 'rounds_played' is how many rounds were played across all of them
+/// * 'wins' is how many of those rounds were won
+/// * 'average_final_balance' is the final balance of a game on average
+#[derive(Clone, Debug)]
+pub struct SimReport {
+    pub games: u32,
+    pub rounds_played: u64,
+    pub wins: u64,
+    pub average_final_balance: f64,
+}
+
+impl SimReport {
+    /// The fraction of the rounds that were won, from 0 to 1 (0 if no rounds were played).
+    pub fn win_rate(&self) -> f64 {
+        if self.rounds_played == 0 {
+            0.0
+        }
+        else {
+            self.wins as f64 / self.rounds_played as f64
+        }
+    }
+}
+
+// Settles a round that was played without the user, the way simulate and run_game_with_strategy
+// count it: lucky and unlucky rounds, the streak bonus, the payout multiplier (or the same
+// multiplier, for a right "same"), and the loss policy all count.  Returns what the round won,
+// or minus what it lost.
+fn settle_auto_round(config: &GameConfig, money: i32, bet: i32, guess: &str, correct: bool, event: Option<Event>, streak: &mut u32) -> i32 {
+    if correct {
+        let mut won = winnings(config, bet, config.variant.guess_multiplier(guess));
+        if event == Some(Event::Lucky) {
+            won = won.saturating_mul(2);
+        }
+        *streak += 1;
+        if *streak >= STREAK_BONUS_THRESHOLD {
+            won = won.saturating_add(share_of_start(config, STREAK_BONUS_SHARE));
+        }
+        won
+    }
+    else {
+        let mut loss = config.loss_policy.loss(bet, money);
+        if event == Some(Event::Unlucky) {
+            loss = loss.saturating_mul(2).min(money);
+        }
+        *streak = 0;
+        -loss
+    }
+}
+
+/// Plays many games without any input or output, to see how a config's rules work out.
+/// Every round bets the smallest bet allowed (or all the money, if that is less) and guesses
+/// SIM_GUESS, which counts as a loss in a variant that doesn't take it.  Winnings are never
+/// risked on double or nothing.  A game ends when the money runs out, when it reaches the
+/// config's cash_out_target, or after the config's max_rounds (SIM_ROUNDS without one).
+/// Lucky and unlucky rounds, the streak bonus, the payout multiplier, the loss policy, and
+/// interest all count, but time limits, loans, and bets riding over several rounds don't.
+///
+/// # Arguments
+///
+/// * `config` - The currency, variant, and rules the games are played with.
+/// * `games` - How many games to simulate.
+/// * `rng` - The random number generator every value in the games is drawn from.
+///
+/// # Returns
+///
+/// * SimReport: the win rate and average final balance over all the games.
+///
+pub fn simulate(config: &mut GameConfig, games: u32, rng: &mut dyn RngCore) -> SimReport {
+    let starting_money = config.starting_balance_override.unwrap_or_else(|| config.currency.starting_amount());
+    let rounds = config.max_rounds.unwrap_or(SIM_ROUNDS);
+    let mut report = SimReport {games, rounds_played: 0, wins: 0, average_final_balance: 0.0};
+    let mut total_balance = 0.0;
+
+    for _ in 0..games {
+        config.variant.reset();
+        let mut money = starting_money;
+        let mut streak = 0;
+        for _ in 0..rounds {
+            if money <= 0 || config.cash_out_target.is_some_and(|target| money >= target) {
+                break;
+            }
+            let bet = config.min_bet.min(money);
+            let event = roll_event(rng);
+            config.variant.generate_first(rng);
+            config.variant.generate_second(rng);
+            report.rounds_played += 1;
+            let correct = config.variant.check_guess(SIM_GUESS).unwrap_or(false);
+            money = money.saturating_add(settle_auto_round(config, money, bet, SIM_GUESS, correct, event, &mut streak));
+            if correct {
+                report.wins += 1;
+            }
+            if config.interest_rate > 0.0 {
+                money = money.saturating_add(interest(config, money, config.interest_rate).max(0));
+            }
+        }
+        total_balance += money as f64;
+    }
+
+    if games > 0 {
+        report.average_final_balance = total_balance / games as f64;
+    }
+    report
+}
+
+
+/// Plays one game with a Strategy making every bet and guess instead of the user, e.g. as a
+/// demo.  The game is printed to the console the way run_game prints it, but nothing is read
+/// from it.  Rounds are played the standard way (see GuppiesVariant::play_round) even for
+/// variants that play them their own way, and a guess the variant doesn't take loses.  The
+/// rules count the way they do in simulate, and winnings are never risked on double or
+/// nothing.  The game always finishes: it ends when the money runs out, when it reaches the
+/// config's cash_out_target, or after the config's max_rounds (SIM_ROUNDS without one).
+///
+/// # Arguments
+///
+/// * `console` - The console the game is printed to.
+/// * `rng` - The random number generator every value in the game is drawn from.
+/// * `config` - The currency, variant, and rules the game is played with.
+/// * `strategy` - What chooses the bets and guesses.
+///
+/// # Returns
+///
+/// * GameResult: the final balance, number of rounds played, and how the game went.
+///
+pub fn run_game_with_strategy(console: &mut Console, rng: &mut dyn RngCore, config: &mut GameConfig, strategy: &mut dyn Strategy) -> GameResult {
+    if config.no_color {
+        colored::control::set_override(false);
+    }
+    let lang = config.lang;
+    let starting_money = config.starting_balance_override.unwrap_or_else(|| config.currency.starting_amount());
+    let rounds = config.max_rounds.unwrap_or(SIM_ROUNDS);
+    let mut money = starting_money;
+    let mut streak = 0;
+    let mut rounds_played = 0;
+    let mut stats = SessionStats {wins: 0, losses: 0, total_wagered: 0, net_change: 0, biggest_win: 0};
+
+    config.variant.reset();
+    console.print(&config.variant.describe());
+
+    while money > 0 {
+        if let Some(target) = config.cash_out_target.filter(|&target| money >= target) {
+            console.print(&fill(msg(MsgKey::CashOutReached, lang), &[&config.currency.format_amount(target)]).bright_green().to_string());
+            break;
+        }
+        if rounds_played >= rounds {
+            console.print(msg(MsgKey::LastRound, lang));
+            break;
+        }
+        config.currency.print_amount(console, money);
+
+        // The strategy's bet is kept to what the rules allow
+        let most = config.max_bet.map_or(money, |max_bet| max_bet.min(money));
+        let bet = strategy.choose_bet(money).max(config.min_bet).min(most);
+        console.print(&fill(msg(MsgKey::StrategyBets, lang), &[&config.currency.format_amount(bet)]));
+
+        let event = roll_event(rng);
+        match event {
+            Some(Event::Lucky) => console.print(&msg(MsgKey::LuckyRound, lang).bright_green().to_string()),
+            Some(Event::Unlucky) => console.print(&msg(MsgKey::UnluckyRound, lang).red().to_string()),
+            None => {}
+        }
+        config.variant.generate_first(rng);
+        config.variant.tell_random(console, DifferentValues::FirstGeneratedVal);
+        let guess = config.variant.strategy_guess(strategy);
+        console.print(&fill(msg(MsgKey::StrategyGuesses, lang), &[&guess]));
+        config.variant.generate_second(rng);
+        let correct = config.variant.check_guess(&guess).unwrap_or(false);
+        config.variant.tell_random(console, DifferentValues::SecondGeneratedVal);
+
+        let change = settle_auto_round(config, money, bet, &guess, correct, event, &mut streak);
+        money = money.saturating_add(change);
+        rounds_played += 1;
+        stats.total_wagered += bet as i64;
+        if correct {
+            console.print(&format!("{} +{}", msg(MsgKey::YouWereRight, lang), config.currency.format_amount(change)).bright_green().to_string());
+            stats.wins += 1;
+            stats.biggest_win = stats.biggest_win.max(change);
+        }
+        else {
+            console.print(&format!("{} -{}", msg(MsgKey::YouWereIncorrect, lang), config.currency.format_amount(-change)).red().to_string());
+            stats.losses += 1;
+        }
+        if config.interest_rate > 0.0 && bet <= config.min_bet {
+            let earned = interest(config, money, config.interest_rate);
+            if earned > 0 {
+                money = money.saturating_add(earned);
+                console.print(&fill(msg(MsgKey::InterestEarned, lang), &[&config.currency.format_amount(earned)]).bright_green().to_string());
+            }
+        }
+    }
+
+    if money <= 0 {
+        console.print(&msg(MsgKey::Broke, lang).red().to_string());
+    }
+    else {
+        console.print(&msg(MsgKey::MadeItOut, lang).bright_green().to_string());
+        console.print(&fill(msg(MsgKey::FinalBalance, lang), &[&format!("{}{}", config.currency.symbol(), config.currency.format_amount(money))]));
+    }
+    stats.net_change = money.saturating_sub(starting_money);
+    print_stats(console, config.currency.as_ref(), &stats, lang);
+
+    GameResult {final_balance: money, rounds_played, quit_early: false, stats, loan_taken: false, undo_used: false}
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+    use std::sync::Mutex;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+    use crate::currency::{CryptoCoin, Dollar, Hbuck, TurkishLira};
+    use crate::storage::{load_balance, save_balance};
+    use crate::strategy::{AlwaysHigher, Conservative};
+    use crate::variants::{FusionGuppies, PlainGuppies, RainbowGuppies, SAME_MULTIPLIER, SquareRootGuppies};
+    use super::*;
+
+    // A variant whose guesses are right or wrong as it is told, so a game's money can be
+    // worked out ahead.  "l" is the other way around, so a game can have both
+    struct Rigged {
+        correct: bool,
+    }
+
+    impl GuppiesVariant for Rigged {
+        fn generate_first(&mut self, _rng: &mut dyn RngCore) {}
+        fn generate_second(&mut self, _rng: &mut dyn RngCore) {}
+        fn display_value(&self, _which: DifferentValues) -> String {
+            "5".to_string()
+        }
+        fn reset(&mut self) {}
+        fn get_guess(&self, console: &mut Console, round: &RoundContext) -> String {
+            self.prompt_guess(console, round, "(h)igher, (l)ower, or the (s)ame?", &self.min_guess_options())
+        }
+        fn min_guess_options(&self) -> Vec<char> {
+            vec!['h', 'l', 's']
+        }
+        fn check_guess(&self, guess: &str) -> Result<bool, GuppiesError> {
+            match guess {
+                "h" | "s" => Ok(self.correct),
+                "l" => Ok(!self.correct),
+                _ => Err(GuppiesError::InvalidGuess(guess.to_string())),
+            }
+        }
+        fn name(&self) -> &'static str {
+            "Rigged"
+        }
+        fn describe(&self) -> String {
+            "Rigged".to_string()
+        }
+        fn hint(&self) -> String {
+            "Hint: rigged.".to_string()
+        }
+    }
+
+    // Makes a game of Dollars starting at $100, with guesses that are always right or always wrong
+    fn rigged_config(correct: bool) -> GameConfig {
+        GameConfig::new(Box::new(Dollar::default()), Box::new(Rigged {correct})).with_no_color()
+    }
+
+    // Whether output is colored is set for the whole test run at once (see no_color), so the
+    // tests that play games take turns
+    static COLOR: Mutex<()> = Mutex::new(());
+
+    // Plays a game on scripted input with a seed that rolls no lucky or unlucky round in the
+    // first 12 rounds, and returns its result along with everything it printed
+    fn play(config: &mut GameConfig, input: &str) -> (GameResult, String) {
+        play_seeded(config, input, 4)
+    }
+
+    // Plays a game the way play does, with the random numbers drawn from seed
+    fn play_seeded(config: &mut GameConfig, input: &str, seed: u64) -> (GameResult, String) {
+        let _color = COLOR.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let mut input = Cursor::new(input.to_string());
+        let mut output = Vec::new();
+        let console = &mut Console { input: &mut input, output: &mut output, quiet: false };
+        let result = run_game(console, &mut StdRng::seed_from_u64(seed), config);
+        (result, String::from_utf8(output).unwrap())
+    }
+
+    // The first seed whose first roll is event.  Rigged draws no random numbers, so with it
+    // the first round of a game played from this seed gets the event
+    fn seed_rolling(event: Event) -> u64 {
+        (0..).find(|&seed| roll_event(&mut StdRng::seed_from_u64(seed)) == Some(event)).unwrap()
+    }
+
+    #[test]
+    fn strategy_can_win_a_fusion_round() {
+        let mut input = Cursor::new(Vec::new());
+        let mut output = Vec::new();
+        let console = &mut Console { input: &mut input, output: &mut output, quiet: true };
+        let variant = FusionGuppies::new(Box::new(PlainGuppies::new()), Box::new(PlainGuppies::new()));
+        let mut config = GameConfig::new(Box::new(Dollar::new(10000)), Box::new(variant)).with_max_rounds(50);
+        let result = run_game_with_strategy(console, &mut StdRng::seed_from_u64(1), &mut config, &mut AlwaysHigher);
+        assert!(result.stats.wins > 0);
+        let printed = String::from_utf8(output).unwrap();
+        assert!(printed.contains("h+h"));
+    }
+
+    #[test]
+    fn run_game_returns_how_the_game_went() {
+        let (result, printed) = play(&mut rigged_config(true), "10\nh\nn\n20\nh\nn\n");
+        assert!(!printed.to_lowercase().contains("lucky round"), "the seed rolled an event:\n{}", printed);
+        assert_eq!(result.final_balance, 130);
+        assert_eq!(result.rounds_played, 2);
+        assert!(result.quit_early);
+    }
+
+    #[test]
+    fn a_right_guess_pays_the_variants_multiplier() {
+        let plain = GameConfig::new(Box::new(Dollar::default()), Box::new(PlainGuppies::new()));
+        assert_eq!(winnings(&plain, 10, plain.variant.guess_multiplier("h")), 10);
+        let rainbow = GameConfig::new(Box::new(Dollar::default()), Box::new(RainbowGuppies::new()));
+        assert_eq!(winnings(&rainbow, 10, rainbow.variant.guess_multiplier("c")), 20);
+    }
+
+    // A path in the temp directory for a test's file, with nothing at it yet
+    fn temp_path(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("guppies_game_{}_{}", std::process::id(), name));
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    #[test]
+    fn only_a_better_balance_is_a_new_high_score() {
+        let path = temp_path("high_score");
+        let (_, printed) = play(&mut rigged_config(true).with_high_score_path(&path), "10\nh\nn\n");
+        assert!(printed.contains("New high score!"));
+        assert_eq!(load_high_score(&path, "Dollars").unwrap(), 110);
+
+        let (_, printed) = play(&mut rigged_config(false).with_high_score_path(&path), "10\nh\n");
+        assert!(!printed.contains("New high score!"));
+        assert_eq!(load_high_score(&path, "Dollars").unwrap(), 110);
+
+        let (_, printed) = play(&mut rigged_config(true).with_high_score_path(&path), "10\nh\nn\n10\nh\nn\n");
+        assert!(printed.contains("New high score!"));
+        assert_eq!(load_high_score(&path, "Dollars").unwrap(), 120);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn the_third_win_in_a_row_earns_the_streak_bonus() {
+        let (result, printed) = play(&mut rigged_config(true), "10\nh\nn\n10\nh\nn\n10\nh\nn\n");
+        assert_eq!(printed.matches("Streak bonus! +10").count(), 1);
+        assert!(printed.contains("Current streak: 3"));
+        assert_eq!(result.final_balance, 140);
+    }
+
+    #[test]
+    fn a_bet_over_the_cap_is_refused_and_one_at_it_is_taken() {
+        let (result, printed) = play(&mut rigged_config(true).with_max_bet(20), "30\n20\nh\nn\n");
+        assert_eq!(printed.matches("The most you can bet is 20.").count(), 1);
+        assert_eq!(result.final_balance, 120);
+        assert_eq!(result.rounds_played, 1);
+    }
+
+    #[test]
+    fn a_game_quit_and_saved_resumes_from_the_same_balance() {
+        let path = temp_path("session");
+        let (result, _) = play(&mut rigged_config(true), "10\nh\nn\n");
+        save_balance(&path, "Dollars", result.final_balance).unwrap();
+
+        let (_, balance) = load_balance(&path).unwrap();
+        let (result, printed) = play(&mut rigged_config(true).with_starting_balance(balance), "");
+        assert!(printed.contains("You currently have $110"));
+        assert_eq!(result.final_balance, 110);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn a_new_config_starts_at_the_currencys_starting_amount() {
+        let mut config = rigged_config(true);
+        assert_eq!(config.max_bet, None);
+        assert_eq!(config.starting_balance_override, None);
+        let (result, printed) = play(&mut config, "");
+        assert!(printed.contains("You currently have $100"));
+        assert_eq!(result.final_balance, 100);
+    }
+
+    #[test]
+    fn a_custom_start_is_the_games_first_balance() {
+        let currencies: Vec<Box<dyn Currency>> = vec![Box::new(Dollar::new(250)), Box::new(TurkishLira::new(5000)), Box::new(Hbuck::new(7))];
+        for currency in currencies {
+            let start = currency.starting_amount();
+            let (result, _) = play(&mut GameConfig::new(currency, Box::new(Rigged {correct: true})), "");
+            assert_eq!(result.final_balance, start);
+        }
+    }
+
+    #[test]
+    fn wins_losses_and_a_low_balance_are_colored() {
+        let _color = COLOR.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        colored::control::set_override(true);
+        let mut output = Vec::new();
+        for (correct, script) in [(true, "10\nh\nn\n"), (false, "90\nh\n")] {
+            let mut input = Cursor::new(script);
+            let console = &mut Console { input: &mut input, output: &mut output, quiet: false };
+            let mut config = GameConfig::new(Box::new(Dollar::default()), Box::new(Rigged {correct}));
+            run_game(console, &mut StdRng::seed_from_u64(4), &mut config);
+        }
+        colored::control::unset_override();
+
+        let printed = String::from_utf8(output).unwrap();
+        assert!(printed.contains(&"You were right! +10".bright_green().to_string()));
+        assert!(printed.contains(&"You were incorrect. -90".red().to_string()));
+        assert!(printed.contains(&"Careful, you're down to 10.".yellow().to_string()));
+        assert!(printed.contains("\u{1b}[92m"));
+    }
+
+    #[test]
+    fn no_color_leaves_out_every_escape_sequence() {
+        let _color = COLOR.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        colored::control::set_override(true);
+        let mut input = Cursor::new("10\nh\nn\n90\nh\n");
+        let mut output = Vec::new();
+        let console = &mut Console { input: &mut input, output: &mut output, quiet: false };
+        let mut config = GameConfig::new(Box::new(Dollar::default()), Box::new(Rigged {correct: true})).with_no_color();
+        run_game(console, &mut StdRng::seed_from_u64(4), &mut config);
+        colored::control::unset_override();
+        assert!(!String::from_utf8(output).unwrap().contains('\u{1b}'));
+    }
+
+    #[test]
+    fn the_player_with_the_most_money_wins() {
+        let _color = COLOR.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let mut input = Cursor::new("10\nh\nn\n30\nh\nn\n");
+        let mut output = Vec::new();
+        let console = &mut Console { input: &mut input, output: &mut output, quiet: false };
+        let players = vec![Player {name: "Ann".to_string(), balance: 100}, Player {name: "Bob".to_string(), balance: 100}];
+        let mut config = rigged_config(true).with_max_rounds(1);
+        let result = run_multiplayer(console, &mut StdRng::seed_from_u64(4), players, &mut config);
+        assert_eq!(result.winner.as_deref(), Some("Bob"));
+        assert_eq!(result.players.iter().map(|player| player.balance).collect::<Vec<_>>(), vec![110, 130]);
+        assert!(String::from_utf8(output).unwrap().contains("Bob wins!"));
+    }
+
+    #[test]
+    fn a_round_limit_ends_the_game_after_that_many_rounds() {
+        let (result, printed) = play(&mut rigged_config(true).with_max_rounds(3), &"10\nh\nn\n".repeat(5));
+        assert_eq!(result.rounds_played, 3);
+        assert_eq!(result.final_balance, 140);
+        assert!(!result.quit_early);
+        assert!(printed.contains("That was the last round."));
+        assert!(printed.contains("You currently have $140."));
+    }
+
+    #[test]
+    fn fractional_bets_stay_exact_over_several_rounds() {
+        let mut config = GameConfig::new(Box::new(CryptoCoin::default()), Box::new(Rigged {correct: true})).with_no_color();
+        let (result, printed) = play(&mut config, &"0.1234\nh\nn\n".repeat(3));
+        // 1.0 to start, three wins of 0.1234, and a streak bonus of 0.1 on the third
+        assert_eq!(result.final_balance, 14702);
+        assert!(printed.contains("You currently have CC1.2468"));
+        assert!(printed.contains("You currently have CC1.4702."));
+    }
+
+    #[test]
+    fn the_stats_add_up_two_wins_and_a_loss() {
+        let (result, printed) = play(&mut rigged_config(true), "10\nh\nn\n20\nh\nn\n5\nl\n");
+        let stats = result.stats;
+        assert_eq!((stats.wins, stats.losses), (2, 1));
+        assert_eq!(stats.total_wagered, 35);
+        assert_eq!(stats.net_change, 25);
+        assert_eq!(stats.biggest_win, 20);
+        assert!(printed.contains("Wins: 2"));
+    }
+
+    #[test]
+    fn input_running_out_ends_the_game() {
+        let (result, _) = play(&mut rigged_config(true), "");
+        assert!(result.quit_early);
+        assert_eq!(result.rounds_played, 0);
+        let (result, _) = play(&mut rigged_config(true), "10\n");
+        assert!(result.quit_early);
+        assert_eq!(result.final_balance, 100);
+    }
+
+    #[test]
+    fn the_log_has_a_line_for_every_round() {
+        let path = temp_path("log.csv");
+        play(&mut rigged_config(true).with_log_path(&path), "10\nh\nn\n20\nl\n");
+        let log = fs::read_to_string(&path).unwrap();
+        assert_eq!(log.lines().collect::<Vec<_>>(), vec!["1,5,h,5,win,110,Rigged", "2,5,l,5,loss,90,Rigged"]);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn a_bet_that_could_win_past_an_i32_is_refused() {
+        let mut config = rigged_config(true).with_starting_balance(i32::MAX - 5);
+        // A right "same" pays 5 times the bet, so a bet of 1 is the most that is safe
+        let (result, printed) = play(&mut config, "9999999999\n2\n1\nh\nn\n");
+        assert!(printed.contains("That number is too far from 0"));
+        assert_eq!(printed.matches("That bet could win more money than the game can count.").count(), 1);
+        assert_eq!(result.final_balance, i32::MAX - 4);
+        assert_eq!(result.rounds_played, 1);
+    }
+
+    #[test]
+    fn double_or_nothing_doubles_or_loses_it_all() {
+        let outcomes: Vec<i32> = (0..20).map(|seed| double_or_nothing(&mut StdRng::seed_from_u64(seed), 15)).collect();
+        assert!(outcomes.iter().all(|&amount| amount == 30 || amount == 0));
+        assert!(outcomes.contains(&30));
+        assert!(outcomes.contains(&0));
+        assert_eq!(double_or_nothing(&mut StdRng::seed_from_u64(3), i32::MAX), if outcomes[3] == 30 { i32::MAX } else { 0 });
+    }
+
+    #[test]
+    fn double_or_nothing_is_only_played_on_d() {
+        let (result, printed) = play(&mut rigged_config(true), "10\nh\nd\n");
+        assert_eq!(result.final_balance, 100);
+        assert!(printed.contains("Nothing! The winnings are gone."));
+        let (result, _) = play(&mut rigged_config(true), "10\nh\nn\n");
+        assert_eq!(result.final_balance, 110);
+    }
+
+    // Scripted input that takes its time with every line, like a user who is slow to type
+    struct SlowInput {
+        lines: Cursor<&'static str>,
+        delay: Duration,
+    }
+
+    impl std::io::Read for SlowInput {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            std::thread::sleep(self.delay);
+            self.lines.read(buf)
+        }
+    }
+
+    impl std::io::BufRead for SlowInput {
+        fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+            std::thread::sleep(self.delay);
+            self.lines.fill_buf()
+        }
+        fn consume(&mut self, amount: usize) {
+            self.lines.consume(amount)
+        }
+    }
+
+    #[test]
+    fn a_guess_past_the_time_limit_loses_the_bet() {
+        let _color = COLOR.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let mut input = SlowInput {lines: Cursor::new("10\nh\n"), delay: Duration::from_millis(200)};
+        let mut output = Vec::new();
+        let console = &mut Console { input: &mut input, output: &mut output, quiet: false };
+        let mut config = rigged_config(true).with_time_limit(Duration::from_millis(20));
+        let result = run_game(console, &mut StdRng::seed_from_u64(4), &mut config);
+        let printed = String::from_utf8(output).unwrap();
+        assert!(printed.contains("Too slow!"));
+        assert_eq!(result.final_balance, 90);
+        assert_eq!(result.stats.losses, 1);
+    }
+
+    #[test]
+    fn fancy_output_prints_the_art_for_how_the_round_went() {
+        let (_, printed) = play(&mut rigged_config(true).with_fancy_output(), "10\nh\nn\n");
+        assert!(printed.contains("YOU WIN!"));
+        let (_, printed) = play(&mut rigged_config(false).with_fancy_output(), "10\nh\n");
+        assert!(printed.contains("better luck next time..."));
+        let (_, printed) = play(&mut rigged_config(true), "10\nh\nn\n");
+        assert!(!printed.contains("YOU WIN!"));
+    }
+
+    #[test]
+    fn each_loss_policy_takes_its_amount() {
+        assert_eq!(LossPolicy::Full.loss(15, 100), 15);
+        // Half rounds toward the player
+        assert_eq!(LossPolicy::Half.loss(15, 100), 7);
+        assert_eq!(LossPolicy::Fixed(5).loss(15, 100), 5);
+        assert_eq!(LossPolicy::Fixed(500).loss(15, 100), 100);
+
+        let (result, _) = play(&mut rigged_config(false).with_loss_policy(LossPolicy::Half), "15\nh\n");
+        assert_eq!(result.final_balance, 93);
+    }
+
+    #[test]
+    fn undo_takes_back_one_round_a_game() {
+        let (result, printed) = play(&mut rigged_config(false), "10\nh\nu\n10\nh\nu\n");
+        assert_eq!(printed.matches("Round undone.").count(), 1);
+        let after_undo = &printed[printed.find("Round undone.").unwrap()..];
+        assert!(after_undo.lines().nth(1).unwrap().contains("You currently have $100"));
+        assert_eq!(result.final_balance, 90);
+        assert!(result.undo_used);
+    }
+
+    #[test]
+    fn a_bet_of_0_is_only_taken_when_the_min_bet_is_0() {
+        let (result, printed) = play(&mut rigged_config(true), "0\n");
+        assert!(printed.contains("The least you can bet is 1."));
+        assert_eq!(result.rounds_played, 0);
+
+        let (result, printed) = play(&mut rigged_config(true).with_min_bet(0), "0\nh\nn\n");
+        assert!(!printed.contains("The least you can bet is"));
+        assert_eq!(result.rounds_played, 1);
+        assert_eq!(result.final_balance, 100);
+    }
+
+    #[test]
+    fn parse_bet_takes_a_percentage_of_the_money() {
+        assert_eq!(parse_bet("50%", 100).unwrap(), 50);
+        assert_eq!(parse_bet("100%", 100).unwrap(), 100);
+        // Rounded down to whole money
+        assert_eq!(parse_bet("33%", 10).unwrap(), 3);
+        assert_eq!(parse_bet("25", 100).unwrap(), 25);
+        assert!(matches!(parse_bet("abc%", 100), Err(GuppiesError::InvalidPercent(input)) if input == "abc%"));
+        assert!(matches!(parse_bet("150%", 100), Err(GuppiesError::PercentTooBig)));
+    }
+
+    #[test]
+    fn a_spanish_game_is_played_in_spanish() {
+        let (_, printed) = play(&mut rigged_config(true).with_lang(Lang::Spanish), "10\nh\nn\n");
+        assert!(printed.contains("¡Acertaste! +10"));
+        assert!(!printed.contains("You were right!"));
+    }
+
+    #[test]
+    fn the_result_json_has_the_balance_rounds_and_wins() {
+        let (result, _) = play(&mut rigged_config(true).with_max_rounds(2), &"10\nh\nn\n".repeat(2));
+        let json: serde_json::Value = serde_json::from_str(&result_to_json(&result)).unwrap();
+        assert_eq!(json["final_balance"], 120);
+        assert_eq!(json["rounds_played"], 2);
+        assert_eq!(json["stats"]["wins"], 2);
+    }
+
+    #[test]
+    fn the_result_is_written_to_the_result_path() {
+        let path = temp_path("result.json");
+        let (result, _) = play(&mut rigged_config(false).with_max_rounds(1).with_result_path(&path), "10\nh\n");
+        let written = std::fs::read_to_string(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(written, result_to_json(&result));
+        assert!(written.contains("\"final_balance\": 90"));
+    }
+
+    #[test]
+    fn a_script_file_plays_a_whole_game() {
+        let path = temp_path("script.txt");
+        std::fs::write(&path, "10\nh\nn\n20\nh\nn\n").unwrap();
+        let mut input = std::io::BufReader::new(std::fs::File::open(&path).unwrap());
+        let mut output = Vec::new();
+        let result = {
+            let _color = COLOR.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+            let console = &mut Console { input: &mut input, output: &mut output, quiet: true };
+            run_game(console, &mut StdRng::seed_from_u64(4), &mut rigged_config(true))
+        };
+        let _ = std::fs::remove_file(&path);
+        // the game quits when the script runs out, after two wins
+        assert_eq!(result.rounds_played, 2);
+        assert_eq!(result.final_balance, 130);
+    }
+
+    #[test]
+    fn a_bet_over_three_rounds_settles_after_the_third() {
+        // 30 is bet once and played for 10 a round: a win, a loss, and a win leave a stake of 40
+        let (result, printed) = play(&mut rigged_config(true).with_bet_rounds(3).with_max_rounds(3), "30\nh\nn\nl\nh\nn\n");
+        assert_eq!(result.rounds_played, 3);
+        assert_eq!(result.final_balance, 110);
+        assert_eq!((result.stats.wins, result.stats.losses), (2, 1));
+        assert_eq!(result.stats.total_wagered, 30);
+        assert_eq!(printed.matches("What is your bet?").count(), 1);
+        assert_eq!(printed.matches("is still riding").count(), 2);
+        assert!(printed.contains("Your bet is settled, 40 comes back to you."));
+    }
+
+    #[test]
+    fn what_doesnt_divide_evenly_is_played_in_the_last_round() {
+        // 32 over 3 rounds is played for 10, 10, and 12, and the third win adds the streak bonus
+        let (result, printed) = play(&mut rigged_config(true).with_bet_rounds(3).with_max_rounds(3), "32\nh\nn\nh\nn\nh\nn\n");
+        assert_eq!(result.stats.total_wagered, 32);
+        assert!(printed.contains("You were right! +12"));
+        assert_eq!(result.final_balance, 142);
+    }
+
+    #[test]
+    fn a_bet_over_several_rounds_needs_the_min_bet_for_each() {
+        let (result, printed) = play(&mut rigged_config(true).with_bet_rounds(3).with_max_rounds(3), "2\n3\nh\nn\nh\nn\nh\nn\n");
+        assert!(printed.contains("The least you can bet is 3."));
+        assert_eq!(result.stats.total_wagered, 3);
+        assert_eq!(result.stats.wins, 3);
+    }
+
+    #[test]
+    fn a_player_who_goes_broke_can_take_the_loan() {
+        // Losing everything, then borrowing 50 and winning 10 with it
+        let (result, printed) = play(&mut rigged_config(false).with_loan_offer(), "100\nh\ny\n10\nl\nn\n");
+        assert!(result.loan_taken);
+        assert!(printed.contains("Take a one-time loan of 50"));
+        assert!(printed.contains("You currently have $50"));
+        assert!(printed.contains("You paid back 50 of your 50 loan."));
+        assert_eq!(result.final_balance, 10);
+    }
+
+    #[test]
+    fn the_loan_is_only_offered_once() {
+        let (result, printed) = play(&mut rigged_config(false).with_loan_offer(), "100\nh\ny\n50\nh\ny\n");
+        assert!(result.loan_taken);
+        assert_eq!(printed.matches("Take a one-time loan").count(), 1);
+        assert_eq!(result.final_balance, 0);
+    }
+
+    #[test]
+    fn custom_win_and_loss_messages_are_printed() {
+        let config = rigged_config(true).with_win_message("Splash!").with_loss_message("Glub glub.");
+        let (_, printed) = play(&mut config.with_max_rounds(2), "10\nh\nn\n10\nl\n");
+        assert!(printed.contains("Splash! +10"));
+        assert!(printed.contains("Glub glub. -10"));
+        assert!(!printed.contains("You were right!") && !printed.contains("You were incorrect."));
+    }
+
+    #[test]
+    fn about_one_round_in_ten_has_an_event() {
+        let mut rng = StdRng::seed_from_u64(6);
+        let events: Vec<Option<Event>> = (0..10000).map(|_| roll_event(&mut rng)).collect();
+        let lucky = events.iter().filter(|&&event| event == Some(Event::Lucky)).count();
+        let unlucky = events.iter().filter(|&&event| event == Some(Event::Unlucky)).count();
+        assert!((400..600).contains(&lucky), "{}", lucky);
+        assert!((400..600).contains(&unlucky), "{}", unlucky);
+    }
+
+    #[test]
+    fn a_lucky_round_doubles_the_winnings() {
+        let (result, printed) = play_seeded(&mut rigged_config(true).with_max_rounds(1), "10\nh\nn\n", seed_rolling(Event::Lucky));
+        assert!(printed.contains("Lucky round"));
+        assert!(printed.contains("+20"));
+        assert_eq!(result.final_balance, 120);
+    }
+
+    #[test]
+    fn an_unlucky_round_doubles_the_loss() {
+        let (result, printed) = play_seeded(&mut rigged_config(false).with_max_rounds(1), "10\nh\n", seed_rolling(Event::Unlucky));
+        assert!(printed.contains("Unlucky round"));
+        assert!(printed.contains("-20"));
+        assert_eq!(result.final_balance, 80);
+    }
+
+    #[test]
+    fn simulated_plain_games_win_higher_about_45_percent_of_the_time() {
+        // The second of two numbers from 1 to 10 is higher 45 times in 100
+        let mut config = GameConfig::new(Box::new(Dollar::default()), Box::new(PlainGuppies::new())).with_max_rounds(10);
+        let report = simulate(&mut config, 1000, &mut StdRng::seed_from_u64(67));
+        assert_eq!(report.games, 1000);
+        assert_eq!(report.rounds_played, 10000);
+        assert!((0.42..0.48).contains(&report.win_rate()), "{}", report.win_rate());
+        assert!(report.average_final_balance > 0.0);
+    }
+
+    #[test]
+    fn history_shows_only_the_last_ten_rounds() {
+        let (_, printed) = play(&mut rigged_config(true), &format!("{}history\n", "10\nh\nn\n".repeat(12)));
+        let history = &printed[printed.find("--- Last rounds ---").unwrap()..];
+        assert!(!history.contains("Round 1:") && !history.contains("Round 2:"));
+        let places: Vec<usize> = (3..=12).map(|round| history.find(&format!("Round {}: bet 10, guessed h, won", round)).unwrap()).collect();
+        assert!(places.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn all_bets_the_whole_balance_once_confirmed() {
+        let (result, printed) = play(&mut rigged_config(true).with_max_rounds(1), "all\ny\nh\nn\n");
+        assert!(printed.contains("+100"));
+        assert_eq!(result.stats.total_wagered, 100);
+        assert_eq!(result.final_balance, 200);
+    }
+
+    #[test]
+    fn all_asks_again_when_it_isnt_confirmed() {
+        let (result, printed) = play(&mut rigged_config(true).with_max_rounds(1), "all\nn\n10\nh\nn\n");
+        assert_eq!(printed.matches("What is your bet?").count(), 2);
+        assert_eq!(result.stats.total_wagered, 10);
+    }
+
+    #[test]
+    fn a_min_bet_round_earns_interest() {
+        // 5% of 105 is 5.25, rounded to 5, and the bigger bet of the second round earns none
+        let mut config = rigged_config(true).with_min_bet(5).with_interest_rate(0.05).with_max_rounds(2);
+        let (result, printed) = play(&mut config, "5\nh\nn\n10\nl\n");
+        assert_eq!(printed.matches("Interest for a careful bet: +5").count(), 1);
+        assert_eq!(result.final_balance, 100);
+    }
+
+    #[test]
+    fn a_fusion_round_with_one_wrong_part_is_lost() {
+        let variant = FusionGuppies::new(Box::new(Rigged {correct: true}), Box::new(Rigged {correct: false}));
+        assert!(!variant.check_guess("h+h").unwrap());
+        assert!(variant.check_guess("h+l").unwrap());
+        assert!(matches!(variant.check_guess("h"), Err(GuppiesError::InvalidGuess(..))));
+
+        let mut config = GameConfig::new(Box::new(Dollar::default()), Box::new(variant)).with_no_color().with_max_rounds(1);
+        let (result, printed) = play(&mut config, "10\nh\nh\n");
+        assert_eq!((result.stats.wins, result.stats.losses), (0, 1));
+        assert_eq!(result.final_balance, 90);
+        assert!(printed.contains("You were incorrect. -10"));
+    }
+
+    #[test]
+    fn a_streak_bonus_past_an_i32_stops_at_the_most_money_there_is() {
+        // The third win's bonus is a share of the huge starting amount, far more than is left
+        let start = i32::MAX - 10;
+        let mut config = GameConfig::new(Box::new(TurkishLira::new(start)), Box::new(Rigged {correct: true})).with_no_color();
+        let (result, printed) = play(&mut config, &"1\nh\nn\n".repeat(3));
+        assert_eq!(result.rounds_played, 3);
+        assert_eq!(result.final_balance, i32::MAX);
+        assert!(printed.contains("Your balance can't go any higher!"));
+    }
+
+    #[test]
+    fn a_declined_quit_goes_back_to_the_guess() {
+        let (result, printed) = play(&mut rigged_config(true).with_max_rounds(1), "10\nq\nn\nh\nn\n");
+        assert!(printed.contains("Are you sure you want to quit? (y/n)"));
+        assert!(!result.quit_early);
+        assert_eq!(result.final_balance, 110);
+    }
+
+    #[test]
+    fn a_confirmed_quit_ends_the_game() {
+        let (result, _) = play(&mut rigged_config(true), "10\nq\ny\n");
+        assert!(result.quit_early);
+        assert_eq!((result.rounds_played, result.final_balance), (0, 100));
+    }
+
+    #[test]
+    fn each_rounding_mode_on_1_2345() {
+        let about = |value: f64, expected: f64| (value - expected).abs() < 1e-9;
+        assert!(about(round_money(1.2345, 2, RoundingMode::Nearest), 1.23));
+        assert!(about(round_money(1.2345, 2, RoundingMode::Floor), 1.23));
+        assert!(about(round_money(1.2345, 2, RoundingMode::Ceil), 1.24));
+        assert!(about(round_money(1.2355, 2, RoundingMode::Nearest), 1.24));
+        assert!(about(round_money(-1.2345, 2, RoundingMode::Floor), -1.24));
+    }
+
+    #[test]
+    fn interest_is_rounded_the_way_the_config_says() {
+        // 5% of 105 is 5.25, which rounds up to 6
+        let mut config = rigged_config(true).with_min_bet(5).with_interest_rate(0.05).with_rounding(RoundingMode::Ceil, 0).with_max_rounds(1);
+        let (result, _) = play(&mut config, "5\nh\nn\n");
+        assert_eq!(result.final_balance, 111);
+    }
+
+    #[test]
+    fn the_game_cashes_out_once_the_target_is_reached() {
+        let (result, printed) = play(&mut rigged_config(true).with_cash_out_target(150), &"25\nh\nn\n".repeat(5));
+        assert_eq!(result.rounds_played, 2);
+        assert_eq!(result.final_balance, 150);
+        assert!(!result.quit_early);
+        assert!(printed.contains("Congratulations, you reached your target of 150!"));
+    }
+
+    #[test]
+    fn an_auto_played_game_finishes_on_its_own() {
+        for strategy in [&mut AlwaysHigher as &mut dyn Strategy, &mut Conservative] {
+            let mut input = std::io::empty();
+            let mut output = Vec::new();
+            let console = &mut Console { input: &mut input, output: &mut output, quiet: true };
+            let mut config = GameConfig::new(Box::new(Dollar::default()), Box::new(PlainGuppies::new()));
+            let result = run_game_with_strategy(console, &mut StdRng::seed_from_u64(89), &mut config, strategy);
+            assert!(result.rounds_played > 0 && result.rounds_played <= SIM_ROUNDS);
+            assert!(result.final_balance == 0 || result.rounds_played == SIM_ROUNDS);
+        }
+    }
+
+    #[test]
+    fn each_milestone_is_announced_once() {
+        // 200 after the first win, 400, then 510 and 530 with the streak bonus
+        let (result, printed) = play(&mut rigged_config(true), "100\nh\nn\n200\nh\nn\n100\nh\nn\n10\nh\nn\n");
+        assert_eq!(result.final_balance, 530);
+        assert_eq!(printed.matches("you've grown your money 2x!").count(), 1);
+        assert_eq!(printed.matches("you've grown your money 5x!").count(), 1);
+        assert!(!printed.contains("10x"));
+    }
+
+    #[test]
+    fn a_declined_bet_is_asked_for_again() {
+        let mut config = rigged_config(true).with_confirm_bets().with_max_rounds(1);
+        let (result, printed) = play(&mut config, "50\nn\n30\ny\nh\nn\n");
+        assert!(printed.contains("Betting 50 of your 100. Confirm? (y/n)"));
+        assert_eq!(result.stats.total_wagered, 30);
+        assert_eq!(result.final_balance, 130);
+    }
+
+    #[test]
+    fn bal_shows_the_balance_without_using_up_the_round() {
+        let (result, printed) = play(&mut rigged_config(true).with_max_rounds(1), "10\nbal\nh\nn\n");
+        let balance = printed.find("You have $100 going into this round.").unwrap();
+        assert!(balance < printed.find("You were right!").unwrap());
+        assert_eq!(result.rounds_played, 1);
+        assert_eq!(result.final_balance, 110);
+    }
+
+    #[test]
+    fn a_right_same_pays_5_times_the_bet() {
+        let (result, printed) = play(&mut rigged_config(true).with_max_rounds(2), "10\ns\nn\n10\nh\nn\n");
+        assert!(printed.contains("You were right! +50"));
+        assert!(printed.contains("You were right! +10"));
+        assert_eq!(result.final_balance, 160);
+        assert_eq!(Rigged {correct: true}.guess_multiplier("s"), SAME_MULTIPLIER);
+        assert!(printed.contains("A right (s)ame guess wins 5 times your bet."));
+
+        // SquareRootGuppies' "s" is a perfect square, so the banner says so
+        let mut input = Cursor::new("");
+        let mut output = Vec::new();
+        let console = &mut Console { input: &mut input, output: &mut output, quiet: false };
+        tell_same_payout(console, &SquareRootGuppies::new(), Lang::English);
+        assert_eq!(String::from_utf8(output).unwrap().trim(), "A right (s)quare guess wins 5 times your bet.");
+    }
+
+    #[test]
+    fn a_quiet_game_prints_the_results_without_the_prompts() {
+        let (result, printed) = play(&mut rigged_config(true).with_quiet().with_max_rounds(2), "10\nh\nn\n10\nl\n");
+        assert_eq!(result.final_balance, 100);
+        let (_, loud) = play(&mut rigged_config(true).with_max_rounds(2), "10\nh\nn\n10\nl\n");
+        for prompt in ["What is your bet?", "(h)igher, (l)ower, or the (s)ame?", "Type d for double or nothing"] {
+            assert!(loud.contains(prompt) && !printed.contains(prompt), "{}", prompt);
+        }
+        assert!(printed.contains("You were right! +10"));
+        assert!(printed.contains("You were incorrect. -10"));
+        assert!(printed.contains("You currently have $110"));
+    }
+}
@@ -25,7 +25,7 @@ fn read_input(prompt: &str) -> String {
     let mut line = String::new();  // buffer for reading input from the user
     io::stdin().read_line(&mut line).expect("Failed to read line");
     let trimmed = line.trim();  // drop whitespace
-    return trimmed.to_string();
+    trimmed.to_string()
 }
 
 /// Prints a given prompt and reads an integer from stdin as an i32.
@@ -61,7 +61,7 @@ fn get_guess() -> String {
         guess = read_input("Invalid guess.  Is the second number (h)igher, (l)ower, or the (s)ame?   [Or (q)uit.]");
         guess = guess.to_lowercase();
     }
-    return guess;
+    guess
 }
 
 /// Checks a guess against the first and second generated numbers in standard Guppies.
@@ -0,0 +1,285 @@
+// lang.rs
+//
+// The message catalog, which holds the game's messages in every language it can be played in.
+//
+
+use serde::Deserialize;  // for reading the language from config files
+
+
+/// Lang is a language the game can be played in
+///
+/// # Variants
+///
+/// * 'English' is the default
+/// * 'Spanish' is español
+#[derive(Clone, Copy, PartialEq, Debug, Default, Deserialize)]
+pub enum Lang {
+    #[default]
+    #[serde(rename = "en")]
+    English,
+    #[serde(rename = "es")]
+    Spanish,
+}
+
+impl Lang {
+    /// Finds the language for a language code, "en" or "es".
+    pub fn from_code(code: &str) -> Option<Lang> {
+        match code {
+            "en" => Some(Lang::English),
+            "es" => Some(Lang::Spanish),
+            _ => None,
+        }
+    }
+}
+
+
+/// MsgKey names each message in the catalog.  Messages with a {} in them are filled in
+/// with fill(), e.g. fill(msg(MsgKey::CurrentStreak, lang), &["3"]).
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum MsgKey {
+    // Betting
+    BetPrompt,
+    BetPromptUndo,
+    TryAgain,
+    MostYouCanBet,
+    LeastYouCanBet,
+    InvalidBet,
+    BetTooBig,
+    RoundUndone,
+    UndoNotScored,
+    AllInPrompt,
+    ConfirmBetPrompt,
+    NoHistory,
+    HistoryHeader,
+    HistoryEntry,
+    HistoryWon,
+    HistoryLost,
+    // Rounds
+    LuckyRound,
+    UnluckyRound,
+    TooSlow,
+    ConfirmQuitPrompt,
+    YouWereRight,
+    StreakBonus,
+    DoubleOrNothingPrompt,
+    DoubleOrNothingAnswer,
+    Doubled,
+    NothingLeft,
+    BalanceMaxed,
+    MilestoneReached,
+    SamePays,
+    YouWereIncorrect,
+    CurrentStreak,
+    PeekBalance,
+    StrategyBets,
+    StrategyGuesses,
+    LowBalance,
+    BetStillRiding,
+    BetSettled,
+    InterestEarned,
+    LoanPrompt,
+    LoanRepaid,
+    // The end of a game
+    CashOutReached,
+    LastRound,
+    Broke,
+    MadeItOut,
+    FinalBalance,
+    StatsHeader,
+    StatsWins,
+    StatsLosses,
+    StatsTotalWagered,
+    StatsNetChange,
+    StatsBiggestWin,
+    NewHighScore,
+    CouldntSaveHighScore,
+    CouldntReadHighScore,
+    CouldntCreateLog,
+    CouldntWriteLog,
+    CouldntWriteResult,
+    // Hot-seat games
+    PlayersTurn,
+    PlayerIsOut,
+    PlayerIsBroke,
+    PlayerFinished,
+    PlayerWins,
+    Tie,
+    // Menus
+    Welcome,
+    ModeMenu,
+    ChooseNumber,
+    ChooseNumberOrHelp,
+    CurrencyHelp,
+    ResumingWith,
+    NoSavedGame,
+    StartingNewGame,
+    CurrencyMenu,
+    VariantMenu,
+    CustomRangePrompt,
+    SmallestNumberPrompt,
+    LargestNumberPrompt,
+    AssistPrompt,
+    PlayerNamePrompt,
+    CashOutPrompt,
+    WalkedAway,
+    SaveBalancePrompt,
+    BalanceSaved,
+    CouldntSaveBalance,
+    YouLasted,
+    YesAnswer,
+    PlayAgainPrompt,
+    PlayAgainOrRestartPrompt,
+    RestartAnswer,
+    Restarting,
+    LeaderboardHeader,
+    LeaderboardEntry,
+}
+
+
+/// Looks up a message in the catalog.
+///
+/// # Arguments
+///
+/// * `key` - Which message to look up.
+/// * `lang` - The language to look it up in.
+///
+pub fn msg(key: MsgKey, lang: Lang) -> &'static str {
+    let (english, spanish) = match key {
+        MsgKey::BetPrompt => ("What is your bet? [Or history to see your last rounds.]", "¿Cuánto apuestas? [O history para ver tus últimas rondas.]"),
+        MsgKey::BetPromptUndo => ("What is your bet? [Or u to undo the last round, or history to see your last rounds.]", "¿Cuánto apuestas? [O u para deshacer la última ronda, o history para ver tus últimas rondas.]"),
+        MsgKey::TryAgain => ("Try again...", "Inténtalo de nuevo..."),
+        MsgKey::MostYouCanBet => ("The most you can bet is {}.", "Lo máximo que puedes apostar es {}."),
+        MsgKey::LeastYouCanBet => ("The least you can bet is {}.", "Lo mínimo que puedes apostar es {}."),
+        MsgKey::InvalidBet => ("Invalid bet.", "Apuesta no válida."),
+        MsgKey::BetTooBig => ("That bet could win more money than the game can count.", "Esa apuesta podría ganar más dinero del que el juego puede contar."),
+        MsgKey::RoundUndone => ("Round undone.  That was this game's only undo, and the game won't count toward the high score or the leaderboard.", "Ronda deshecha.  Era el único deshacer de esta partida, y la partida no contará para el récord ni la clasificación."),
+        MsgKey::UndoNotScored => ("A round was undone, so this game doesn't count toward the high score.", "Se deshizo una ronda, así que esta partida no cuenta para el récord."),
+        MsgKey::AllInPrompt => ("Bet all {} you have? Are you sure? (y/n)", "¿Apostar los {} que tienes? ¿Seguro? (s/n)"),
+        MsgKey::ConfirmBetPrompt => ("Betting {} of your {}. Confirm? (y/n)", "Apuestas {} de tus {}. ¿Confirmas? (s/n)"),
+        MsgKey::NoHistory => ("No rounds have been played yet.", "Todavía no se ha jugado ninguna ronda."),
+        MsgKey::HistoryHeader => ("--- Last rounds ---", "--- Últimas rondas ---"),
+        MsgKey::HistoryEntry => ("Round {}: bet {}, guessed {}, {}, balance {}", "Ronda {}: apuesta {}, elegiste {}, {}, saldo {}"),
+        MsgKey::HistoryWon => ("won", "ganaste"),
+        MsgKey::HistoryLost => ("lost", "perdiste"),
+        MsgKey::LuckyRound => ("Lucky round — winnings doubled!", "¡Ronda de suerte: las ganancias se duplican!"),
+        MsgKey::UnluckyRound => ("Unlucky round — losses doubled!", "Ronda de mala suerte: las pérdidas se duplican."),
+        MsgKey::TooSlow => ("Too slow!", "¡Demasiado lento!"),
+        MsgKey::ConfirmQuitPrompt => ("Are you sure you want to quit? (y/n)", "¿Seguro que quieres salir? (s/n)"),
+        MsgKey::YouWereRight => ("You were right!", "¡Acertaste!"),
+        MsgKey::StreakBonus => ("Streak bonus! +{}", "¡Bono de racha! +{}"),
+        MsgKey::DoubleOrNothingPrompt => ("Type d for double or nothing on your winnings, or anything else to keep them.", "Escribe d para doble o nada con tus ganancias, o cualquier otra cosa para quedártelas."),
+        MsgKey::DoubleOrNothingAnswer => ("d", "d"),
+        MsgKey::Doubled => ("Doubled! +{}", "¡Doble! +{}"),
+        MsgKey::NothingLeft => ("Nothing! The winnings are gone.", "¡Nada! Las ganancias se fueron."),
+        MsgKey::BalanceMaxed => ("Your balance can't go any higher!", "¡Tu saldo no puede subir más!"),
+        MsgKey::MilestoneReached => ("Incredible, you've grown your money {}x!  You're up to {}.", "¡Increíble, multiplicaste tu dinero por {}!  Ya tienes {}."),
+        MsgKey::SamePays => ("A right {} guess wins {} times your bet.", "Acertar con {} gana {} veces tu apuesta."),
+        MsgKey::YouWereIncorrect => ("You were incorrect.", "Fallaste."),
+        MsgKey::CurrentStreak => ("Current streak: {}", "Racha actual: {}"),
+        MsgKey::PeekBalance => ("You have {} going into this round.", "Tienes {} al empezar esta ronda."),
+        MsgKey::StrategyBets => ("The computer bets {}.", "La computadora apuesta {}."),
+        MsgKey::StrategyGuesses => ("The computer guesses {}.", "La computadora adivina {}."),
+        MsgKey::LowBalance => ("Careful, you're down to {}.", "Cuidado, solo te quedan {}."),
+        MsgKey::BetStillRiding => ("Your bet of {} is still riding for {} more rounds.", "Tu apuesta de {} sigue en juego durante {} rondas más."),
+        MsgKey::BetSettled => ("Your bet is settled, {} comes back to you.", "Tu apuesta se liquida, te devuelve {}."),
+        MsgKey::InterestEarned => ("Interest for a careful bet: +{}", "Intereses por una apuesta prudente: +{}"),
+        MsgKey::LoanPrompt => ("You're out of money!  Take a one-time loan of {} to keep playing? (y/n)", "¡Te quedaste sin dinero!  ¿Aceptas un préstamo único de {} para seguir jugando? (s/n)"),
+        MsgKey::LoanRepaid => ("You paid back {} of your {} loan.", "Devolviste {} de tu préstamo de {}."),
+        MsgKey::CashOutReached => ("Congratulations, you reached your target of {}!  Time to cash out.", "¡Felicidades, alcanzaste tu meta de {}!  Es hora de cobrar."),
+        MsgKey::LastRound => ("That was the last round.", "Esa fue la última ronda."),
+        MsgKey::Broke => ("You're broke. :-/", "Te quedaste sin dinero. :-/"),
+        MsgKey::MadeItOut => ("You made it out!", "¡Saliste a tiempo!"),
+        MsgKey::FinalBalance => ("You currently have {}.", "Ahora tienes {}."),
+        MsgKey::StatsHeader => ("--- Session stats ---", "--- Estadísticas de la sesión ---"),
+        MsgKey::StatsWins => ("Wins: {}", "Aciertos: {}"),
+        MsgKey::StatsLosses => ("Losses: {}", "Fallos: {}"),
+        MsgKey::StatsTotalWagered => ("Total wagered: {}", "Total apostado: {}"),
+        MsgKey::StatsNetChange => ("Net change: {}", "Cambio neto: {}"),
+        MsgKey::StatsBiggestWin => ("Biggest win: {}", "Mayor ganancia: {}"),
+        MsgKey::NewHighScore => ("New high score!", "¡Nuevo récord!"),
+        MsgKey::CouldntSaveHighScore => ("Couldn't save the high score:", "No se pudo guardar el récord:"),
+        MsgKey::CouldntReadHighScore => ("Couldn't read the high score:", "No se pudo leer el récord:"),
+        MsgKey::CouldntCreateLog => ("Couldn't create the round log:", "No se pudo crear el registro de rondas:"),
+        MsgKey::CouldntWriteLog => ("Couldn't write to the round log:", "No se pudo escribir en el registro de rondas:"),
+        MsgKey::CouldntWriteResult => ("Couldn't write the game's result:", "No se pudo escribir el resultado del juego:"),
+        MsgKey::PlayersTurn => ("{}'s turn!", "¡Turno de {}!"),
+        MsgKey::PlayerIsOut => ("{} is out with {}.", "{} se retira con {}."),
+        MsgKey::PlayerIsBroke => ("{} is broke. :-/", "{} se quedó sin dinero. :-/"),
+        MsgKey::PlayerFinished => ("{} finished with {}.", "{} terminó con {}."),
+        MsgKey::PlayerWins => ("{} wins!", "¡{} gana!"),
+        MsgKey::Tie => ("It's a tie!", "¡Es un empate!"),
+        MsgKey::Welcome => ("Welcome to Guppies!", "¡Bienvenido a Guppies!"),
+        MsgKey::ModeMenu => ("1: New game, 2: Resume previous game, 3: Two-player game", "1: Juego nuevo, 2: Continuar el juego anterior, 3: Juego de dos jugadores"),
+        MsgKey::ChooseNumber => ("Choose the correct corresponding number:", "Elige el número correspondiente:"),
+        MsgKey::ChooseNumberOrHelp => ("Choose the correct corresponding number or name, or h for help:", "Elige el número o el nombre correspondiente, o h para ver la ayuda:"),
+        MsgKey::CurrencyHelp => ("{}: {}, you start with {}.", "{}: {}, empiezas con {}."),
+        MsgKey::ResumingWith => ("Resuming with a balance of {}.", "Continuando con un saldo de {}."),
+        MsgKey::NoSavedGame => ("No saved game found.", "No hay ningún juego guardado."),
+        MsgKey::StartingNewGame => ("Starting a new game...", "Empezando un juego nuevo..."),
+        MsgKey::CurrencyMenu => ("Choose a currency: 1: Dollar, 2: Turkish Lira, 3: Hbucks, 4: Euro, 5: CryptoCoin", "Elige una moneda: 1: Dólar, 2: Lira turca, 3: Hbucks, 4: Euro, 5: CryptoCoin"),
+        MsgKey::VariantMenu => ("Choose a GameMode: {}", "Elige un modo de juego: {}"),
+        MsgKey::CustomRangePrompt => ("Use a custom number range? (y/n)", "¿Usar un rango de números propio? (s/n)"),
+        MsgKey::SmallestNumberPrompt => ("What is the smallest number?", "¿Cuál es el número más pequeño?"),
+        MsgKey::LargestNumberPrompt => ("What is the largest number?", "¿Cuál es el número más grande?"),
+        MsgKey::AssistPrompt => ("Show the better guess after each first value? (y/n)", "¿Mostrar la mejor opción después de cada primer valor? (s/n)"),
+        MsgKey::PlayerNamePrompt => ("What is player {}'s name?", "¿Cómo se llama el jugador {}?"),
+        MsgKey::CashOutPrompt => ("Cash out automatically once you have how much?   [Or press Enter to play until you quit.]", "¿Con cuánto quieres cobrar automáticamente?   [O pulsa Enter para jugar hasta que salgas.]"),
+        MsgKey::WalkedAway => ("You walked away after {} rounds with {} left.", "Te retiraste después de {} rondas con {} restantes."),
+        MsgKey::SaveBalancePrompt => ("Save your balance to resume later? (y/n)", "¿Guardar tu saldo para continuar después? (s/n)"),
+        MsgKey::BalanceSaved => ("Balance saved.", "Saldo guardado."),
+        MsgKey::CouldntSaveBalance => ("Couldn't save your balance:", "No se pudo guardar tu saldo:"),
+        MsgKey::YouLasted => ("You lasted {} rounds.", "Duraste {} rondas."),
+        MsgKey::YesAnswer => ("y", "s"),
+        MsgKey::PlayAgainPrompt => ("Play again? (y/n)", "¿Jugar otra vez? (s/n)"),
+        MsgKey::PlayAgainOrRestartPrompt => ("Play again? (y/n, or r to restart with the same currency and variant)", "¿Jugar otra vez? (s/n, o r para empezar de nuevo con la misma moneda y variante)"),
+        MsgKey::RestartAnswer => ("r", "r"),
+        MsgKey::Restarting => ("Starting over with {} and {}.", "Empezando de nuevo con {} y {}."),
+        MsgKey::LeaderboardHeader => ("--- Leaderboard ---", "--- Clasificación ---"),
+        MsgKey::LeaderboardEntry => ("{}. {} after {} rounds", "{}. {} después de {} rondas"),
+    };
+    match lang {
+        Lang::English => english,
+        Lang::Spanish => spanish,
+    }
+}
+
+/// Fills in the {}s in a message from the catalog, in order.  Only the template's own {}s
+/// are filled, so a value with a {} in it (like a player's name) is put in as it is.  A {}
+/// left without a value stays a {}.
+///
+/// # Arguments
+///
+/// * `template` - The message, with a {} for each value.
+/// * `values` - The values to put in place of the {}s.
+///
+pub fn fill(template: &str, values: &[&str]) -> String {
+    let mut pieces = template.split("{}");
+    let mut filled = pieces.next().unwrap_or_default().to_string();
+    let mut values = values.iter();
+    for piece in pieces {
+        filled.push_str(values.next().copied().unwrap_or("{}"));
+        filled.push_str(piece);
+    }
+    filled
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn messages_come_in_the_language_asked_for() {
+        assert_eq!(msg(MsgKey::YouWereRight, Lang::Spanish), "¡Acertaste!");
+        assert_eq!(msg(MsgKey::YouWereRight, Lang::English), "You were right!");
+        assert_eq!(msg(MsgKey::YouWereRight, Lang::default()), "You were right!");
+    }
+
+    #[test]
+    fn fill_puts_the_values_in_order() {
+        assert_eq!(fill(msg(MsgKey::Restarting, Lang::Spanish), &["Euros", "PlainGuppies"]), "Empezando de nuevo con Euros y PlainGuppies.");
+        assert_eq!(fill("{} and {}", &["a", "b"]), "a and b");
+        assert_eq!(fill("{} and {}", &["{}", "b"]), "{} and b");
+        assert_eq!(fill("{} and {}", &["a"]), "a and {}");
+    }
+}
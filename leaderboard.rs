@@ -0,0 +1,126 @@
+// leaderboard.rs
+//
+// The session leaderboard, which ranks the games played since the program started.
+//
+
+use std::cmp::Reverse;  // for ranking the highest balance first
+use crate::game::GameResult;
+
+
+/// Leaderboard keeps the result of every game played in a session, so they can be ranked
+/// by final balance between games.  Balances in different currencies can't be compared, so
+/// games are only ranked against others played in the same currency.
+///
+/// # Fields
+///
+/// * 'results' is every game's result with the name of its currency, in the order the games
+///   were played
+pub struct Leaderboard {
+    results: Vec<(String, GameResult)>,
+}
+
+impl Leaderboard {
+    /// Makes an empty leaderboard.
+    pub fn new() -> Leaderboard {
+        Leaderboard {results: Vec::new()}
+    }
+
+    /// Adds a finished game to the leaderboard, unless a round of it was undone.
+    ///
+    /// # Arguments
+    ///
+    /// * `currency` - The name of the currency the game was played in, as Currency::name gives it.
+    /// * `result` - The result run_game handed back.
+    ///
+    pub fn record(&mut self, currency: &str, result: GameResult) {
+        if !result.undo_used {
+            self.results.push((currency.to_string(), result));
+        }
+    }
+
+    /// Lists the currencies games have been played in, in the order they were first played.
+    pub fn currencies(&self) -> Vec<&str> {
+        let mut currencies: Vec<&str> = Vec::new();
+        for (currency, _) in &self.results {
+            if !currencies.contains(&currency.as_str()) {
+                currencies.push(currency);
+            }
+        }
+        currencies
+    }
+
+    /// Finds the best games so far in a currency.
+    ///
+    /// # Arguments
+    ///
+    /// * `currency` - The name of the currency, as Currency::name gives it.
+    /// * `n` - How many games to return, at most.
+    ///
+    /// # Returns
+    ///
+    /// * Vec: up to n results, highest final balance first.  Games that tied keep the order
+    ///   they were played in.
+    ///
+    pub fn top(&self, currency: &str, n: usize) -> Vec<&GameResult> {
+        let mut ranked: Vec<&GameResult> = self.results.iter().filter(|(name, _)| name == currency).map(|(_, result)| result).collect();
+        ranked.sort_by_key(|result| Reverse(result.final_balance));
+        ranked.truncate(n);
+        ranked
+    }
+
+    /// How many games have been recorded.
+    pub fn len(&self) -> usize {
+        self.results.len()
+    }
+
+    /// Whether no games have been recorded yet.
+    pub fn is_empty(&self) -> bool {
+        self.results.is_empty()
+    }
+}
+
+impl Default for Leaderboard {
+    fn default() -> Leaderboard {
+        Leaderboard::new()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::game::SessionStats;
+    use super::*;
+
+    // A finished game that ended with final_balance
+    fn result(final_balance: i32) -> GameResult {
+        let stats = SessionStats {wins: 0, losses: 0, total_wagered: 0, net_change: 0, biggest_win: 0};
+        GameResult {final_balance, rounds_played: 1, quit_early: false, stats, loan_taken: false, undo_used: false}
+    }
+
+    #[test]
+    fn top_ranks_the_highest_balance_first() {
+        let mut leaderboard = Leaderboard::new();
+        for balance in [120, 300, 80] {
+            leaderboard.record("Dollars", result(balance));
+        }
+        let top: Vec<i32> = leaderboard.top("Dollars", 2).iter().map(|result| result.final_balance).collect();
+        assert_eq!(top, vec![300, 120]);
+        assert_eq!(leaderboard.len(), 3);
+    }
+
+    #[test]
+    fn currencies_are_ranked_separately() {
+        let mut leaderboard = Leaderboard::new();
+        leaderboard.record("Dollars", result(120));
+        leaderboard.record("Euros", result(500));
+        assert_eq!(leaderboard.currencies(), vec!["Dollars", "Euros"]);
+        assert_eq!(leaderboard.top("Dollars", 5).len(), 1);
+    }
+
+    #[test]
+    fn an_undone_game_is_left_off() {
+        let mut leaderboard = Leaderboard::new();
+        leaderboard.record("Dollars", GameResult {undo_used: true, ..result(1000)});
+        assert!(leaderboard.is_empty());
+    }
+}
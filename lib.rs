@@ -0,0 +1,112 @@
+// guppies
+//
+// Author: Harsh Patel, Mark Liffiton
+// Date: November, 2022
+//
+// The Guppies game as a library.  Guppies is a game where the user bets on how a second
+// random value compares to the first one, in whichever currency and variant they choose.
+// The guppies binary is a thin wrapper around play_interactive().
+//
+
+// FYI, Rust comments starting with /// are "doc comments," specifically for building
+// documentation.  Regular comments (not for building documentation, but just to be read in the
+// code itself, start with //.
+
+use std::io;  // for playing on stdin and stdout
+use std::io::BufReader;  // for buffering stdin
+
+mod art;
+mod config;
+mod console;
+mod currency;
+mod error;
+mod ev;
+mod game;
+mod lang;
+mod leaderboard;
+mod menu;
+mod round_log;
+mod storage;
+mod strategy;
+mod variants;
+
+pub use config::{ConfigError, load_config, parse_config};
+pub use console::{
+    Console, normalize_guess, read_amount_input, read_guess_input, read_input, read_input_by, read_int_input,
+    read_menu_choice,
+};
+pub use currency::{CryptoCoin, Currency, Dollar, Euro, Hbuck, TurkishLira, format_fixed_point, format_money};
+pub use error::GuppiesError;
+pub use ev::{describe_expected_value, expected_value};
+pub use game::{
+    Event, GameConfig, GameResult, LossPolicy, MultiplayerResult, Player, RoundingMode, SessionStats, SimReport,
+    double_or_nothing, parse_bet, result_to_json, roll_event, round_money, run_game, run_game_with_strategy,
+    run_multiplayer, simulate,
+};
+pub use lang::{Lang, MsgKey, fill, msg};
+pub use leaderboard::Leaderboard;
+pub use menu::{parse_currency_choice, parse_variant_choice, play_interactive};
+pub use round_log::RoundLog;
+pub use storage::{HIGH_SCORE_FILE, SESSION_FILE, SaveFiles, load_balance, load_high_score, save_balance, save_high_score};
+pub use strategy::{AlwaysHigher, Conservative, Strategy};
+pub use variants::{
+    AlphabetGuppies, ChainGuppies, ColorDistanceGuppies, ComparatorGuppies, DateBasedGuppies, DiceGuppies,
+    DifferentValues, FibonacciGuppies, FusionGuppies, GuppiesVariant, HIGHER_LOWER_SAME_WORDS, Manyguppies,
+    ModularArithmeticGuppies, OddOrEvenGuppies, PlainGuppies, PrimeGuppies, ProbabilityGuppies, RainbowGuppies,
+    RainbowNumberGuppies, RomanNumeralGuppies, RoundContext, RoundOutcome, RunningTotalGuppies, SquareRootGuppies,
+    SumGuppies, VariantConstructor, odds, optimal_guess, variant_registry,
+};
+
+
+/// Plays one game of Guppies on stdin and stdout with the given config, skipping the menus.
+/// Nothing is saved to disk, so this is the entry point for embedding the game.
+///
+/// # Arguments
+///
+/// * `config` - The currency, variant, and rules to play with.
+///
+/// # Returns
+///
+/// * GameResult: the final balance, number of rounds played, and whether the player quit
+///
+pub fn play(mut config: GameConfig) -> GameResult {
+    let mut input = BufReader::new(io::stdin());
+    let mut output = io::stdout();
+    let console = &mut Console { input: &mut input, output: &mut output, quiet: false };
+    run_game(console, &mut rand::thread_rng(), &mut config)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+    use super::*;
+
+    #[test]
+    fn a_scripted_game_plays_to_the_end() {
+        // After a loss the "n" meant for double or nothing is taken as a bet and refused, so
+        // the script works whichever way the rounds go
+        let mut input = Cursor::new("10\nh\nn\n".repeat(3));
+        let mut output = Vec::new();
+        let console = &mut Console { input: &mut input, output: &mut output, quiet: true };
+        let mut config = GameConfig::new(Box::new(Dollar::default()), Box::new(PlainGuppies::new())).with_max_rounds(3);
+        let result = run_game(console, &mut StdRng::seed_from_u64(21), &mut config);
+        assert_eq!(result.rounds_played, 3);
+        assert!(!result.quit_early);
+    }
+
+    #[test]
+    fn the_same_seed_and_answers_play_out_the_same_game() {
+        let play = |seed| {
+            let mut input = Cursor::new("10\nh\nn\n".repeat(8));
+            let mut output = Vec::new();
+            let console = &mut Console { input: &mut input, output: &mut output, quiet: true };
+            let mut config = GameConfig::new(Box::new(Dollar::default()), Box::new(PlainGuppies::new())).with_max_rounds(8);
+            result_to_json(&run_game(console, &mut StdRng::seed_from_u64(seed), &mut config))
+        };
+        assert_eq!(play(69), play(69));
+        assert_ne!(play(69), play(70));
+    }
+}
@@ -0,0 +1,547 @@
+// menu.rs
+//
+// The interactive menus for starting a game of Guppies from the terminal.
+//
+
+use std::fs;  // for removing a session file once it has been resumed
+use std::ops::RangeInclusive;  // for the range of valid menu choices
+use rand::RngCore;  // for the random number generator the games are played with
+use colored::*;  // for coloring printed output
+use crate::console::{Console, parse_amount, read_input, read_int_input, read_menu_choice};
+use crate::currency::{CryptoCoin, Currency, Dollar, Euro, Hbuck, TurkishLira};
+use crate::error::GuppiesError;
+use crate::variants::{GuppiesVariant, OddOrEvenGuppies, PlainGuppies, variant_registry};
+use crate::game::{GameConfig, Player, run_game, run_multiplayer};
+use crate::lang::{Lang, MsgKey, fill, msg};
+use crate::leaderboard::Leaderboard;
+use crate::storage::{SaveFiles, load_balance, save_balance};
+
+
+// The currencies' names, in the order the currency menu lists them
+const CURRENCY_NAMES: [&str; 5] = ["Dollar", "Turkish Lira", "Hbucks", "Euro", "CryptoCoin"];
+
+// How many games the leaderboard shows
+const LEADERBOARD_SIZE: usize = 5;
+
+// How many variants the variant menu lists, one for each in the registry
+fn variant_choices() -> i32 {
+    variant_registry().len() as i32
+}
+
+
+// Finds the choice a name stands for: a whole name, or any word of it, with or without the
+// "Guppies" on the end, ignoring case and spaces.  "turkish", "lira", and "TurkishLira" all
+// name Turkish Lira, and "plain" names PlainGuppies.
+fn choice_for_name(input: &str, names: &[&str]) -> Option<i32> {
+    let input = input.trim().to_lowercase().replace([' ', '_'], "");
+    if input.is_empty() {
+        return None;
+    }
+    let position = names.iter().position(|name| {
+        let name = name.to_lowercase();
+        let whole = name.replace(' ', "");
+        whole == input || whole.strip_suffix("guppies") == Some(input.as_str()) || name.split(' ').any(|word| word == input)
+    })?;
+    Some(position as i32 + 1)
+}
+
+/// Reads a choice on the currency menu, typed as its number or its name (see the menu).
+/// Names ignore case, and one word of a name is enough, e.g. "2", "lira", and "TURKISH" all
+/// choose Turkish Lira.
+///
+/// # Arguments
+///
+/// * `input` - The text the user typed.
+///
+/// # Returns
+///
+/// * Option: the number of the currency chosen, or None if the input doesn't choose one.
+///
+pub fn parse_currency_choice(input: &str) -> Option<i32> {
+    match input.trim().parse::<i32>() {
+        Ok(choice) if (1..=CURRENCY_NAMES.len() as i32).contains(&choice) => Some(choice),
+        Ok(..) => None,
+        Err(..) => choice_for_name(input, &CURRENCY_NAMES),
+    }
+}
+
+/// Reads a choice on the variant menu, typed as its number or its name, with or without the
+/// "Guppies", e.g. "1", "plain", and "PlainGuppies" all choose PlainGuppies.  Names ignore case.
+///
+/// # Arguments
+///
+/// * `input` - The text the user typed.
+///
+/// # Returns
+///
+/// * Option: the number of the variant chosen, or None if the input doesn't choose one.
+///
+pub fn parse_variant_choice(input: &str) -> Option<i32> {
+    match input.trim().parse::<i32>() {
+        Ok(choice) if (1..=variant_choices()).contains(&choice) => Some(choice),
+        Ok(..) => None,
+        Err(..) => {
+            let names: Vec<&str> = variant_registry().iter().map(|&(name, _)| name).collect();
+            choice_for_name(input, &names)
+        }
+    }
+}
+
+
+// Makes the currency for a choice on the currency menu, with its usual starting amount
+fn currency_for_choice(choice: i32) -> Box<dyn Currency> {
+    match choice {
+        1 => Box::new(Dollar::default()),
+        2 => Box::new(TurkishLira::default()),
+        3 => Box::new(Hbuck::default()),
+        4 => Box::new(Euro::default()),
+        5 => Box::new(CryptoCoin::default()),
+        _ => unreachable!("read_menu_choice_with_help only returns valid choices")
+    }
+}
+
+// Finds the choice on the currency menu for a currency's name, as Currency::name gives it
+fn currency_choice_named(name: &str) -> Option<i32> {
+    (1..=CURRENCY_NAMES.len() as i32).find(|&choice| currency_for_choice(choice).name() == name)
+}
+
+// Makes the variant for a choice on the variant menu, from the variant registry.  PlainGuppies
+// is the standard 1 to 10 game here, the menu asks about a custom range on its own
+fn variant_for_choice(choice: i32) -> Box<dyn GuppiesVariant> {
+    let (_, make) = variant_registry()[choice as usize - 1];
+    make()
+}
+
+// Prints each variant's rules, numbered the way the variant menu numbers them
+fn print_variant_help(console: &mut Console) {
+    for choice in 1..=variant_choices() {
+        console.print(&format!("{}: {}", choice, variant_for_choice(choice).describe()));
+    }
+}
+
+
+/// Shows a menu and reads a choice from it the way read_menu_choice does, except that "h"
+/// (or "help") prints the help for the menu's choices and then shows the menu again, and a
+/// choice can be typed as its name too.  Returns None if the input runs out.
+///
+/// # Arguments
+///
+/// * `console` - The console to show the menu on and read the choice from.
+/// * `menu` - The menu's choices, printed before every prompt.
+/// * `valid_range` - The numbers of the menu's choices.
+/// * `lang` - The language to prompt in.
+/// * `help` - Prints the help for the menu's choices.
+/// * `parse_choice` - Reads a choice typed as its number or its name, like parse_currency_choice.
+///
+fn read_menu_choice_with_help(console: &mut Console, menu: &str, valid_range: RangeInclusive<i32>, lang: Lang, help: &dyn Fn(&mut Console), parse_choice: fn(&str) -> Option<i32>) -> Option<i32> {
+    console.print(menu);
+    loop {
+        let line = read_input(console, msg(MsgKey::ChooseNumberOrHelp, lang))?;
+        if matches!(line.to_lowercase().as_str(), "h" | "help") {
+            help(console);
+            console.print(menu);
+            continue;
+        }
+        if let Some(choice) = parse_choice(&line) {
+            return Some(choice);
+        }
+        let message = match parse_amount(&line, 0) {
+            Ok(..) => format!("Please enter a number from {} to {}.", valid_range.start(), valid_range.end()),
+            Err(..) if !line.trim().is_empty() => format!("'{}' isn't the number or name of a choice.", line.trim()),
+            Err(error) => error.to_string(),
+        };
+        console.print(&format!("{}  {}", message.red(), msg(MsgKey::TryAgain, lang)));
+    }
+}
+
+
+/// Prints the best games of the session, highest final balance first, ranked separately for
+/// each currency they were played in.
+///
+/// # Arguments
+///
+/// * `console` - The console to print the leaderboard to.
+/// * `leaderboard` - The session's games.
+/// * `lang` - The language to print in.
+///
+fn print_leaderboard(console: &mut Console, leaderboard: &Leaderboard, lang: Lang) {
+    console.print(&msg(MsgKey::LeaderboardHeader, lang).bright_purple().to_string());
+    for name in leaderboard.currencies() {
+        // Every game on the leaderboard was played in one of the menu's currencies
+        let Some(currency) = currency_choice_named(name).map(currency_for_choice) else {
+            continue;
+        };
+        console.print(&format!("{}:", name));
+        for (place, result) in (1..).zip(leaderboard.top(name, LEADERBOARD_SIZE)) {
+            let balance = currency.format_amount(result.final_balance);
+            console.print(&fill(msg(MsgKey::LeaderboardEntry, lang), &[&place.to_string(), &balance, &result.rounds_played.to_string()]));
+        }
+    }
+}
+
+
+/// Asks whether to draw a variant's numbers from a custom range, and if so reads one, asking
+/// again as long as the range isn't valid.  Returns None if the input runs out.
+///
+/// # Arguments
+///
+/// * `console` - The console to ask the user through.
+/// * `lang` - The language to ask in.
+/// * `standard` - Makes the variant with its standard range.
+/// * `with_range` - Makes the variant with a custom range, or says what's wrong with it.
+///
+fn read_range<V>(console: &mut Console, lang: Lang, standard: fn() -> V, with_range: fn(i32, i32) -> Result<V, GuppiesError>) -> Option<V> {
+    let custom = read_input(console, msg(MsgKey::CustomRangePrompt, lang))?.to_lowercase();
+    if custom != msg(MsgKey::YesAnswer, lang) {
+        return Some(standard());
+    }
+    loop {
+        let min = read_int_input(console, msg(MsgKey::SmallestNumberPrompt, lang))?;
+        let max = read_int_input(console, msg(MsgKey::LargestNumberPrompt, lang))?;
+        match with_range(min, max) {
+            Ok(variant) => return Some(variant),
+            Err(error) => console.print(&format!("{}  {}", error.to_string().red(), msg(MsgKey::TryAgain, lang))),
+        }
+    }
+}
+
+/// Builds the PlainGuppies game for the main menu, letting the user pick a custom range
+/// instead of the standard 1 to 10 and turn on assist mode.  Returns None if the input runs out.
+///
+/// # Arguments
+///
+/// * `console` - The console to ask the user through.
+/// * `lang` - The language to ask in.
+///
+fn read_plain_guppies(console: &mut Console, lang: Lang) -> Option<PlainGuppies> {
+    let plain = read_range(console, lang, PlainGuppies::new, PlainGuppies::with_range)?;
+    let assist = read_input(console, msg(MsgKey::AssistPrompt, lang))?.to_lowercase();
+    if assist == msg(MsgKey::YesAnswer, lang) {
+        return Some(plain.with_assist());
+    }
+    Some(plain)
+}
+
+
+// Asks for the balance to cash out at, where just pressing Enter means playing without one.
+// The outer None is the input running out.
+fn read_cash_out_target(console: &mut Console, lang: Lang, decimal_places: u32) -> Option<Option<i32>> {
+    loop {
+        let line = read_input(console, msg(MsgKey::CashOutPrompt, lang))?;
+        if line.is_empty() {
+            return Some(None);
+        }
+        match parse_amount(&line, decimal_places) {
+            Ok(target) => return Some(Some(target)),
+            Err(error) => console.print(&format!("{}  {}", error.to_string().red(), msg(MsgKey::TryAgain, lang))),
+        }
+    }
+}
+
+
+/// Plays games of Guppies the way the guppies binary does, one after another for as long as
+/// the user wants to play again.  Each game is ranked on a leaderboard for the session.
+///
+/// # Arguments
+///
+/// * `console` - The console the menus and the games are played on.
+/// * `rng` - The random number generator every value in the games is drawn from.
+/// * `lang` - The language the menus and the games' messages are printed in.
+/// * `currency` - The number of the currency to play with (as on the currency menu), or None
+///   to ask for it.
+/// * `variant` - The number of the variant to play (as on the variant menu), or None to ask for
+///   it.  A variant chosen this way is played with its usual settings.
+/// * `files` - Where the high score and the saved balance are kept (SaveFiles::default() for
+///   the current directory).
+///
+/// When either is given, every game is a new one-player game and the mode menu is skipped.
+/// When both are, the game starts without asking anything, and plays without a cash-out target.
+/// After a one-player game, "r" at the play again prompt restarts it with the same currency
+/// and variant (and cash-out target) without going through the menus.
+///
+/// # Returns
+///
+/// * Leaderboard: the results of every one-player game played.
+///
+pub fn play_interactive(console: &mut Console, rng: &mut dyn RngCore, lang: Lang, currency: Option<i32>, variant: Option<i32>, files: &SaveFiles) -> Leaderboard {
+    console.print(&msg(MsgKey::Welcome, lang).bright_purple().to_string());
+    let mut leaderboard = Leaderboard::new();
+    // The config of the last one-player game, kept so it can be restarted
+    let mut last_game = None;
+    let mut playing = play_one_game(console, rng, lang, &mut leaderboard, currency, variant, files, &mut last_game);
+    while playing {
+        let prompt = if last_game.is_some() { MsgKey::PlayAgainOrRestartPrompt } else { MsgKey::PlayAgainPrompt };
+        let again = read_input(console, msg(prompt, lang)).unwrap_or_default().to_lowercase();
+        playing = match last_game.as_mut() {
+            Some(config) if again == msg(MsgKey::RestartAnswer, lang) => {
+                // A restart is a fresh game, so a resumed balance isn't carried over
+                config.starting_balance_override = None;
+                console.print(&fill(msg(MsgKey::Restarting, lang), &[config.currency.name(), config.variant.name()]));
+                play_config(console, rng, lang, &mut leaderboard, config, files);
+                true
+            }
+            _ if again == msg(MsgKey::YesAnswer, lang) => play_one_game(console, rng, lang, &mut leaderboard, currency, variant, files, &mut last_game),
+            _ => false,
+        };
+    }
+    leaderboard
+}
+
+
+/// Plays one game from the menus: offers to resume a saved game, walks the user through
+/// choosing a mode, a currency, and a variant, runs the game, and offers to save the balance
+/// if they quit.  A one-player game is added to the leaderboard, which is printed after it.
+///
+/// # Arguments
+///
+/// * `console` - The console the menus and the game are played on.
+/// * `rng` - The random number generator every value in the game is drawn from.
+/// * `lang` - The language the menus and the game's messages are printed in.
+/// * `leaderboard` - The session's leaderboard, which the game is added to.
+/// * `currency_choice` - The currency chosen ahead of time, if any (see play_interactive).
+/// * `variant_choice` - The variant chosen ahead of time, if any (see play_interactive).
+/// * `files` - Where the high score and the saved balance are kept.
+/// * `last_game` - Set to the config of a one-player game once it is played, so it can be
+///   restarted, and to None for a two-player game.
+///
+/// # Returns
+///
+/// * bool: True if a game was played, False if the input ran out in the menus.
+///
+#[allow(clippy::too_many_arguments)]
+fn play_one_game(console: &mut Console, rng: &mut dyn RngCore, lang: Lang, leaderboard: &mut Leaderboard, currency_choice: Option<i32>, variant_choice: Option<i32>, files: &SaveFiles, last_game: &mut Option<GameConfig>) -> bool {
+    let choose_number = msg(MsgKey::ChooseNumber, lang);
+
+    // Lets the user pick up a game they saved when they quit last time, unless the game was
+    // chosen ahead of time, which always makes it a new one
+    let mut starting_balance = None;
+    let mode = if currency_choice.is_some() || variant_choice.is_some() {
+        1
+    }
+    else {
+        console.print(msg(MsgKey::ModeMenu, lang));
+        // Running out of input anywhere in the menus just leaves without playing
+        let Some(mode) = read_menu_choice(console, choose_number, 1..=3) else {
+            return false;
+        };
+        mode
+    };
+    // A resumed game is played in the currency its balance was saved in, so that menu is skipped
+    let mut currency_choice = currency_choice;
+    if mode == 2 {
+        let saved = load_balance(&files.session).ok().and_then(|(name, balance)| Some((currency_choice_named(&name)?, balance)));
+        match saved {
+            Some((choice, balance)) => {
+                let currency = currency_for_choice(choice);
+                let amount = format!("{}{}", currency.symbol(), currency.format_amount(balance));
+                console.print(&fill(msg(MsgKey::ResumingWith, lang), &[&amount]));
+                starting_balance = Some(balance);
+                currency_choice = Some(choice);
+                // A saved game can only be resumed once
+                let _ = fs::remove_file(&files.session);
+            }
+            None => console.print(&format!("{}  {}", msg(MsgKey::NoSavedGame, lang).red(), msg(MsgKey::StartingNewGame, lang))),
+        }
+    }
+
+    // Lets the user choose a currency, with h describing each of them first
+    let currency_help = |console: &mut Console| {
+        for (choice, name) in (1..).zip(CURRENCY_NAMES) {
+            let currency = currency_for_choice(choice);
+            let start = currency.format_amount(currency.starting_amount());
+            console.print(&fill(msg(MsgKey::CurrencyHelp, lang), &[&choice.to_string(), name, &start]));
+        }
+    };
+    let currency_choice_given = currency_choice.is_some();
+    let currency_choice = match currency_choice {
+        Some(choice) => choice,
+        None => match read_menu_choice_with_help(console, msg(MsgKey::CurrencyMenu, lang), 1..=CURRENCY_NAMES.len() as i32, lang, &currency_help, parse_currency_choice) {
+            Some(choice) => choice,
+            None => return false,
+        },
+    };
+    let currency = currency_for_choice(currency_choice);
+
+    // Lets the user choose a variant, with h printing each variant's rules first
+    let choices: Vec<String> = (1..).zip(variant_registry()).map(|(choice, (name, _))| format!("{}:{}", choice, name)).collect();
+    let variant_menu = fill(msg(MsgKey::VariantMenu, lang), &[&choices.join(", ")]);
+    let variant : Box<dyn GuppiesVariant> = match variant_choice {
+        Some(choice) => variant_for_choice(choice),
+        None => {
+            let Some(choice) = read_menu_choice_with_help(console, &variant_menu, 1..=variant_choices(), lang, &print_variant_help, parse_variant_choice) else {
+                return false;
+            };
+            // PlainGuppies and OddOrEvenGuppies have settings of their own, so they ask about
+            // them here.  They are found by name, so the menu's order doesn't matter
+            let (name, _) = variant_registry()[choice as usize - 1];
+            match name {
+                "PlainGuppies" => match read_plain_guppies(console, lang) {
+                    Some(plain) => Box::new(plain),
+                    None => return false,
+                },
+                "OddOrEvenGuppies" => match read_range(console, lang, OddOrEvenGuppies::new, OddOrEvenGuppies::with_range) {
+                    Some(odd_or_even) => Box::new(odd_or_even),
+                    None => return false,
+                },
+                _ => variant_for_choice(choice),
+            }
+        }
+    };
+    // Two players share the console and take turns, starting with the currency's usual amount
+    if mode == 3 {
+        let mut players = Vec::new();
+        for number in 1..=2 {
+            let Some(name) = read_input(console, &fill(msg(MsgKey::PlayerNamePrompt, lang), &[&number.to_string()])) else {
+                return false;
+            };
+            players.push(Player {name, balance: currency.starting_amount()});
+        }
+        let mut config = GameConfig::new(currency, variant).with_lang(lang);
+        run_multiplayer(console, rng, players, &mut config);
+        *last_game = None;
+        return true;
+    }
+
+    // A game chosen entirely ahead of time starts straight away, so it plays without a cash-out target
+    let cash_out_target = if currency_choice_given && variant_choice.is_some() {
+        None
+    }
+    else {
+        match read_cash_out_target(console, lang, currency.decimal_places()) {
+            Some(cash_out_target) => cash_out_target,
+            None => return false,
+        }
+    };
+
+    // Bundles the choices into a config, keeping the high score and any resumed balance
+    let mut config = GameConfig::new(currency, variant).with_high_score_path(&files.high_score).with_loan_offer().with_lang(lang);
+    config.starting_balance_override = starting_balance;
+    config.cash_out_target = cash_out_target;
+    play_config(console, rng, lang, leaderboard, &mut config, files);
+    *last_game = Some(config);
+    true
+}
+
+
+// Runs a one-player game with the config chosen on the menus, offers to save the balance to
+// the session file if the user quits, and prints the leaderboard with the game added to it
+fn play_config(console: &mut Console, rng: &mut dyn RngCore, lang: Lang, leaderboard: &mut Leaderboard, config: &mut GameConfig, files: &SaveFiles) {
+    let result = run_game(console, rng, config);
+
+    // Sums up the game using what run_game returned
+    if result.quit_early {
+        let left = config.currency.format_amount(result.final_balance);
+        console.print(&fill(msg(MsgKey::WalkedAway, lang), &[&result.rounds_played.to_string(), &left]));
+
+        // Offers to keep the balance around so the game can be resumed from the main menu
+        let save = read_input(console, msg(MsgKey::SaveBalancePrompt, lang)).unwrap_or_default().to_lowercase();
+        if save == msg(MsgKey::YesAnswer, lang) {
+            match save_balance(&files.session, config.currency.name(), result.final_balance) {
+                Ok(()) => console.print(msg(MsgKey::BalanceSaved, lang)),
+                Err(error) => console.print(&format!("{} {}", msg(MsgKey::CouldntSaveBalance, lang).red(), error)),
+            }
+        }
+    }
+    else {
+        console.print(&fill(msg(MsgKey::YouLasted, lang), &[&result.rounds_played.to_string()]));
+    }
+
+    // Ranks the game against the others played this session
+    leaderboard.record(config.currency.name(), result);
+    print_leaderboard(console, leaderboard, lang);
+}
+
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use super::*;
+
+    // Files in the temp directory for a test's high score and session, with nothing at them yet
+    fn temp_files(name: &str) -> SaveFiles {
+        let path = |file: &str| std::env::temp_dir().join(format!("guppies_menu_{}_{}_{}", std::process::id(), name, file));
+        let files = SaveFiles {high_score: path("high_score"), session: path("session")};
+        let _ = fs::remove_file(&files.high_score);
+        let _ = fs::remove_file(&files.session);
+        files
+    }
+
+    #[test]
+    fn the_currency_menu_takes_euros() {
+        assert_eq!(parse_currency_choice("4"), Some(4));
+        assert_eq!(parse_currency_choice("euro"), Some(4));
+        assert_eq!(currency_for_choice(4).name(), "Euros");
+    }
+
+    #[test]
+    fn help_on_the_variant_menu_comes_before_the_choice() {
+        let mut input = std::io::Cursor::new("h\n1\n");
+        let mut output = Vec::new();
+        let mut console = Console { input: &mut input, output: &mut output, quiet: false };
+        let choice = read_menu_choice_with_help(&mut console, "1:PlainGuppies", 1..=variant_choices(), Lang::English, &print_variant_help, parse_variant_choice);
+        assert_eq!(choice, Some(1));
+        let output = String::from_utf8(output).unwrap();
+        let rules = output.find(&PlainGuppies::new().describe()).expect("the help describes PlainGuppies");
+        assert!(output.matches("1:PlainGuppies").count() == 2 && rules < output.rfind("1:PlainGuppies").unwrap());
+    }
+
+    #[test]
+    fn play_again_plays_another_game() {
+        // Two games of standard PlainGuppies in dollars, each lost on a bet of everything, so
+        // neither one sets a high score.  The loan is turned down each time, and then the
+        // second "Play again?" is answered no
+        let mut input = std::io::Cursor::new("100\ns\nn\ny\n100\ns\nn\nn\n");
+        let mut output = Vec::new();
+        let mut console = Console { input: &mut input, output: &mut output, quiet: true };
+        let mut rng = rand::rngs::StdRng::seed_from_u64(0);
+        let files = temp_files("play_again");
+        let leaderboard = play_interactive(&mut console, &mut rng, Lang::English, Some(1), Some(1), &files);
+        assert!(!files.high_score.exists() && !files.session.exists());
+        assert_eq!(leaderboard.len(), 2);
+        let balances: Vec<i32> = leaderboard.top("Dollars", 5).iter().map(|result| result.final_balance).collect();
+        assert_eq!(balances, vec![0, 0]);
+        assert_eq!(String::from_utf8(output).unwrap().matches("You lasted 1 rounds.").count(), 2);
+    }
+
+    #[test]
+    fn currencies_can_be_chosen_by_name() {
+        for input in ["2", "lira", "TURKISH", " Turkish Lira ", "turkishlira"] {
+            assert_eq!(parse_currency_choice(input), Some(2), "{}", input);
+        }
+        assert_eq!(parse_currency_choice("pesos"), None);
+        assert_eq!(parse_currency_choice("9"), None);
+    }
+
+    #[test]
+    fn variants_can_be_chosen_by_name() {
+        assert_eq!(parse_variant_choice("1"), Some(1));
+        assert_eq!(parse_variant_choice("plain"), Some(1));
+        assert_eq!(parse_variant_choice("RainbowGuppies"), Some(2));
+        assert_eq!(parse_variant_choice(""), None);
+    }
+
+    #[test]
+    fn restart_plays_the_same_variant_again() {
+        // A standard PlainGuppies game in dollars lost on a bet of everything, restarted with
+        // r, and lost the same way, so neither game sets a high score
+        let mut input = std::io::Cursor::new("100\ns\nn\nr\n100\ns\nn\nn\n");
+        let mut output = Vec::new();
+        let mut console = Console { input: &mut input, output: &mut output, quiet: true };
+        let files = temp_files("restart");
+        let leaderboard = play_interactive(&mut console, &mut rand::rngs::StdRng::seed_from_u64(0), Lang::English, Some(1), Some(1), &files);
+        assert!(!files.high_score.exists() && !files.session.exists());
+        let balances: Vec<i32> = leaderboard.top("Dollars", 5).iter().map(|result| result.final_balance).collect();
+        assert_eq!(balances, vec![0, 0]);
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("Starting over with Dollars and PlainGuppies."));
+        assert_eq!(output.matches(&PlainGuppies::new().describe()).count(), 2);
+    }
+
+    #[test]
+    fn the_variants_with_settings_are_in_the_registry() {
+        // The variant menu finds the variants that ask about their settings by these names
+        for name in ["PlainGuppies", "OddOrEvenGuppies"] {
+            assert_eq!(parse_variant_choice(name).map(|choice| variant_for_choice(choice).name()), Some(name));
+        }
+    }
+}
@@ -0,0 +1,88 @@
+// round_log.rs
+//
+// The replay log, which records every round of a game to a file.
+//
+
+use std::fs::File;  // for creating the log file
+use std::io;  // for the errors writing the log can give
+use std::io::{BufWriter, Write};  // for writing the log a line at a time
+use std::path::Path;  // for pointing at the log file
+use crate::variants::{DifferentValues, GuppiesVariant};
+
+
+/// RoundLog writes one comma-separated line per round to a log file:
+/// round number, first value, guess, second value, result (win or loss), balance after the
+/// round, and the variant played.  e.g. "3,7,h,9,win,120,PlainGuppies"
+/// A field with a comma, quote, or line break in it (like a DateBasedGuppies date) is quoted
+/// the CSV way, e.g. as "Tuesday, March 14" in quotes, so every line has the same columns.
+///
+/// # Fields
+///
+/// * 'writer' is the buffered log file the lines are written to
+pub struct RoundLog {
+    writer: BufWriter<File>,
+}
+
+impl RoundLog {
+    /// Creates the log file, replacing any log that was already at path.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the log file.
+    ///
+    /// # Returns
+    ///
+    /// * io::Result: the new log, or an error if the file can't be created.
+    ///
+    pub fn create(path: &Path) -> io::Result<RoundLog> {
+        Ok(RoundLog {writer: BufWriter::new(File::create(path)?)})
+    }
+
+    /// Writes the line for one round.
+    ///
+    /// # Arguments
+    ///
+    /// * `round` - The round's number, counting from 1.
+    /// * `variant` - The variant the round was played in, for its two values the way the user
+    ///   saw them and its name.
+    /// * `guess` - The user's guess.
+    /// * `correct` - Whether the guess was right.
+    /// * `balance` - The user's money after the round was settled.
+    ///
+    pub fn record(&mut self, round: u32, variant: &dyn GuppiesVariant, guess: &str, correct: bool, balance: i32) -> io::Result<()> {
+        let first = variant.display_value(DifferentValues::FirstGeneratedVal);
+        let second = variant.display_value(DifferentValues::SecondGeneratedVal);
+        let result = if correct { "win" } else { "loss" };
+        writeln!(self.writer, "{},{},{},{},{},{},{}", round, csv_field(&first), csv_field(guess), csv_field(&second), result, balance, csv_field(variant.active_name()))
+    }
+
+    /// Flushes the last lines to the file and closes it.  Dropping a RoundLog flushes too, but
+    /// any error doing so is lost, so games call this at the end instead.
+    pub fn finish(mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+// Quotes a field that would otherwise break the line into the wrong columns, doubling any
+// quotes inside it (RFC 4180).  Fields without commas, quotes, or line breaks are left bare
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    }
+    else {
+        field.to_string()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fields_that_would_break_the_line_are_quoted() {
+        assert_eq!(csv_field("7"), "7");
+        assert_eq!(csv_field("Tuesday, March 14"), "\"Tuesday, March 14\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+}
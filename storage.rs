@@ -0,0 +1,168 @@
+// storage.rs
+//
+// The small files Guppies keeps in between sessions: the high score and a saved balance.
+//
+
+use std::io;  // for the errors reading and writing files can give
+use std::fs;  // for reading and writing the files
+use std::path::{Path, PathBuf};  // for pointing at the files
+
+
+// The file the interactive game keeps the best balance in between sessions
+pub const HIGH_SCORE_FILE: &str = "guppies_high_score.txt";
+
+// Reads every currency's high score from a high score file, which holds one line per currency
+// of its name and its best balance in its smallest unit, e.g. "Dollars 250".  A missing file
+// just means nobody has set a high score yet.  A line without a currency name (a file from
+// before scores were kept per currency) is skipped, since its currency isn't known.
+fn load_high_scores(path: &Path) -> io::Result<Vec<(String, i32)>> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(error) => return Err(error),
+    };
+    let mut scores = Vec::new();
+    for line in contents.lines().map(str::trim).filter(|line| !line.is_empty()) {
+        let Some((currency, score)) = line.rsplit_once(' ') else {
+            continue;
+        };
+        let score = score.parse::<i32>().map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+        scores.push((currency.to_string(), score));
+    }
+    Ok(scores)
+}
+
+/// Reads the stored high score for a currency.  Each currency has a score of its own, since
+/// balances in different currencies (counted in their smallest units) can't be compared.  A
+/// currency nobody has set a high score in yet counts as 0.
+///
+/// # Arguments
+///
+/// * `path` - The path of the high score file.
+/// * `currency` - The name of the currency, as Currency::name gives it.
+///
+/// # Returns
+///
+/// * io::Result: the high score, or an error if the file can't be read or a score in it isn't
+///   an integer.
+///
+pub fn load_high_score(path: &Path, currency: &str) -> io::Result<i32> {
+    let scores = load_high_scores(path)?;
+    Ok(scores.into_iter().find(|(name, _)| name == currency).map_or(0, |(_, score)| score))
+}
+
+/// Writes a currency's high score to the high score file, replacing its old one and keeping
+/// the other currencies' scores.
+///
+/// # Arguments
+///
+/// * `path` - The path of the high score file.
+/// * `currency` - The name of the currency, as Currency::name gives it.
+/// * `score` - The new high score.
+///
+pub fn save_high_score(path: &Path, currency: &str, score: i32) -> io::Result<()> {
+    let mut scores = load_high_scores(path)?;
+    scores.retain(|(name, _)| name != currency);
+    scores.push((currency.to_string(), score));
+    let lines: Vec<String> = scores.iter().map(|(name, score)| format!("{} {}", name, score)).collect();
+    fs::write(path, lines.join("\n") + "\n")
+}
+
+// The file a quitting player's balance is saved to, so they can resume later
+pub const SESSION_FILE: &str = "guppies_session.txt";
+
+/// Where the interactive game keeps its files in between sessions.  The default is
+/// HIGH_SCORE_FILE and SESSION_FILE in the current directory.
+///
+/// # Fields
+///
+/// * 'high_score' is the file the best balance in each currency is kept in.
+/// * 'session' is the file a quitting player's balance is saved to.
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct SaveFiles {
+    pub high_score: PathBuf,
+    pub session: PathBuf,
+}
+
+impl Default for SaveFiles {
+    fn default() -> SaveFiles {
+        SaveFiles {high_score: PathBuf::from(HIGH_SCORE_FILE), session: PathBuf::from(SESSION_FILE)}
+    }
+}
+
+/// Saves a balance to a session file with the name of its currency, like a line of the high
+/// score file, so the game can be resumed later in the same currency.
+///
+/// # Arguments
+///
+/// * `path` - The path of the session file.
+/// * `currency` - The name of the balance's currency, as Currency::name gives it.
+/// * `balance` - The balance to save, in the currency's smallest unit.
+///
+pub fn save_balance(path: &Path, currency: &str, balance: i32) -> io::Result<()> {
+    fs::write(path, format!("{} {}", currency, balance))
+}
+
+/// Reads the balance saved by save_balance.  Unlike the high score, a missing file is an
+/// error here, since it means there is no game to resume.  So is a balance without its
+/// currency (saved before balances kept one), since it can't be told what it is worth.
+///
+/// # Arguments
+///
+/// * `path` - The path of the session file.
+///
+/// # Returns
+///
+/// * io::Result: the name of the saved balance's currency and the balance, or an error if
+///   there isn't a readable one.
+///
+pub fn load_balance(path: &Path) -> io::Result<(String, i32)> {
+    let contents = fs::read_to_string(path)?;
+    let Some((currency, balance)) = contents.trim().rsplit_once(' ') else {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "the saved balance has no currency"));
+    };
+    let balance = balance.parse::<i32>().map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+    Ok((currency.to_string(), balance))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A path in the temp directory for a test's file, with nothing at it yet
+    fn temp_path(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("guppies_storage_{}_{}", std::process::id(), name));
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    #[test]
+    fn a_missing_high_score_file_is_a_score_of_0() {
+        assert_eq!(load_high_score(&temp_path("missing_high_score"), "Dollars").unwrap(), 0);
+    }
+
+    #[test]
+    fn a_saved_high_score_can_be_loaded() {
+        let path = temp_path("high_score");
+        save_high_score(&path, "Dollars", 150).unwrap();
+        assert_eq!(load_high_score(&path, "Dollars").unwrap(), 150);
+        save_high_score(&path, "Dollars", 200).unwrap();
+        assert_eq!(load_high_score(&path, "Dollars").unwrap(), 200);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn a_saved_balance_loads_back_with_its_currency() {
+        let path = temp_path("session");
+        save_balance(&path, "Turkish Lira", 4321).unwrap();
+        assert_eq!(load_balance(&path).unwrap(), ("Turkish Lira".to_string(), 4321));
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn there_is_no_balance_to_load_without_a_session_file() {
+        assert!(load_balance(&temp_path("missing_session")).is_err());
+    }
+}
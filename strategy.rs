@@ -0,0 +1,76 @@
+// strategy.rs
+//
+// Ways for the computer to play Guppies on its own, e.g. for a demo (see run_game_with_strategy).
+//
+
+
+/// Strategy is a way of playing Guppies without anyone at the keyboard
+///
+/// # Methods
+///
+/// * 'choose_bet' is how much to bet out of balance, counted in the currency's smallest unit.
+///   The game keeps the bet between the min bet and the most that can be bet
+/// * 'choose_guess' is the guess once the first value is out, as its letter.  It is handed
+///   the variant's hint for the first value (see GuppiesVariant::hint) to go on, and a variant
+///   whose guesses are made of parts asks once per part (see GuppiesVariant::strategy_guess)
+pub trait Strategy {
+    fn choose_bet(&mut self, balance: i32) -> i32;
+    fn choose_guess(&mut self, first_value_hint: &str) -> char;
+}
+
+
+// AlwaysHigher bets a tenth of the balance every round and always guesses higher, whatever
+// the first value is
+#[derive(Default)]
+pub struct AlwaysHigher;
+
+impl Strategy for AlwaysHigher {
+    fn choose_bet(&mut self, balance: i32) -> i32 {
+        balance / 10
+    }
+
+    fn choose_guess(&mut self, _first_value_hint: &str) -> char {
+        'h'
+    }
+}
+
+
+// Conservative bets as little as it can, and guesses higher unless the hint says higher is
+// unlikely, in which case it guesses lower
+#[derive(Default)]
+pub struct Conservative;
+
+impl Strategy for Conservative {
+    // Anything under the min bet is raised to it, so this is always the smallest bet allowed
+    fn choose_bet(&mut self, _balance: i32) -> i32 {
+        1
+    }
+
+    fn choose_guess(&mut self, first_value_hint: &str) -> char {
+        if first_value_hint.contains("higher is unlikely") || first_value_hint.contains("higher is impossible") {
+            'l'
+        }
+        else {
+            'h'
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn always_higher_bets_a_tenth_and_guesses_higher() {
+        assert_eq!(AlwaysHigher.choose_bet(250), 25);
+        assert_eq!(AlwaysHigher.choose_guess("Hint: higher is impossible, lower is likely, the same is unlikely."), 'h');
+    }
+
+    #[test]
+    fn conservative_bets_1_and_follows_the_hint() {
+        assert_eq!(Conservative.choose_bet(1000), 1);
+        assert_eq!(Conservative.choose_guess("Hint: higher is impossible, lower is likely, the same is unlikely."), 'l');
+        assert_eq!(Conservative.choose_guess("Hint: higher is likely, lower is impossible, the same is unlikely."), 'h');
+    }
+}
@@ -0,0 +1,522 @@
+// variants.rs
+//
+// The GuppiesVariant trait, which every way of playing Guppies implements.  Each variant
+// lives in its own file under variants/.
+//
+
+use std::cell::Cell;  // for a round to note that its time ran out
+use std::time::Instant;  // for the deadline on a round's guesses
+use rand::RngCore;  // for generating random numbers
+use colored::*;  // for coloring printed output
+use crate::console::{Console, normalize_guess, read_guess_input, read_input};
+use crate::error::GuppiesError;
+use crate::ev::describe_expected_value;
+use crate::lang::{Lang, MsgKey, msg};
+use crate::strategy::Strategy;
+
+mod alphabet;
+mod chain;
+mod color_distance;
+mod comparator;
+mod date_based;
+mod dice;
+mod fibonacci;
+mod fusion;
+mod many;
+mod modular;
+mod odd_or_even;
+mod plain;
+mod prime;
+mod probability;
+mod rainbow;
+mod rainbow_number;
+mod roman;
+mod running_total;
+mod square_root;
+mod sum;
+
+pub use alphabet::AlphabetGuppies;
+pub use chain::ChainGuppies;
+pub use color_distance::ColorDistanceGuppies;
+pub use comparator::ComparatorGuppies;
+pub use date_based::DateBasedGuppies;
+pub use dice::DiceGuppies;
+pub use fibonacci::FibonacciGuppies;
+pub use fusion::FusionGuppies;
+pub use many::Manyguppies;
+pub use modular::ModularArithmeticGuppies;
+pub use odd_or_even::OddOrEvenGuppies;
+pub use plain::{PlainGuppies, optimal_guess};
+pub use prime::PrimeGuppies;
+pub use probability::{ProbabilityGuppies, odds};
+pub use rainbow::RainbowGuppies;
+pub use rainbow_number::RainbowNumberGuppies;
+pub use roman::RomanNumeralGuppies;
+pub use running_total::RunningTotalGuppies;
+pub use square_root::SquareRootGuppies;
+pub use sum::SumGuppies;
+
+
+/// A function making a variant with its usual settings, as variant_registry lists them.
+pub type VariantConstructor = fn() -> Box<dyn GuppiesVariant>;
+
+/// Lists every variant there is, in the order the variant menu shows them, so adding a
+/// variant here is all it takes to put it on the menu.  Each comes with its name (as name()
+/// says it) and a function making it with its usual settings.  ManyGuppies picks from plain,
+/// rainbow, and odd or even every round, and FusionGuppies plays plain and rainbow together.
+///
+/// # Returns
+///
+/// * Vec: the name and constructor of every variant.
+///
+pub fn variant_registry() -> Vec<(&'static str, VariantConstructor)> {
+    vec![
+        ("PlainGuppies", || Box::new(PlainGuppies::new())),
+        ("RainbowGuppies", || Box::new(RainbowGuppies::new())),
+        ("OddOrEvenGuppies", || Box::new(OddOrEvenGuppies::new())),
+        ("Manyguppies", || Box::new(Manyguppies::new(vec![Box::new(PlainGuppies::new()), Box::new(RainbowGuppies::new()), Box::new(OddOrEvenGuppies::new())]))),
+        ("ChainGuppies", || Box::new(ChainGuppies::new())),
+        ("DateBasedGuppies", || Box::new(DateBasedGuppies::new())),
+        ("PrimeGuppies", || Box::new(PrimeGuppies::new())),
+        ("ModularArithmeticGuppies", || Box::new(ModularArithmeticGuppies::new())),
+        ("FibonacciGuppies", || Box::new(FibonacciGuppies::new())),
+        ("SumGuppies", || Box::new(SumGuppies::new())),
+        ("RomanNumeralGuppies", || Box::new(RomanNumeralGuppies::new())),
+        ("ComparatorGuppies", || Box::new(ComparatorGuppies::new())),
+        ("SquareRootGuppies", || Box::new(SquareRootGuppies::new())),
+        ("ColorDistanceGuppies", || Box::new(ColorDistanceGuppies::new())),
+        ("AlphabetGuppies", || Box::new(AlphabetGuppies::new())),
+        ("ProbabilityGuppies", || Box::new(ProbabilityGuppies::new())),
+        ("FusionGuppies", || Box::new(FusionGuppies::new(Box::new(PlainGuppies::new()), Box::new(RainbowGuppies::new())))),
+        ("RunningTotalGuppies", || Box::new(RunningTotalGuppies::new())),
+        ("DiceGuppies", || Box::new(DiceGuppies::new())),
+        ("RainbowNumberGuppies", || Box::new(RainbowNumberGuppies::new())),
+    ]
+}
+
+
+/// How many times the bet a right "same" guess wins, unless the variant says otherwise.
+pub const SAME_MULTIPLIER: f64 = 5.0;
+
+/// The share of a break-even payout a guess paid by its odds wins (see odds_multiplier), so
+/// the house keeps a small edge on every guess.
+pub const ODDS_PAYOUT_SHARE: f64 = 0.95;
+
+/// The most a guess paid by its odds can win, as a multiple of the bet, so a long shot can't
+/// pay without limit.
+pub const MAX_ODDS_MULTIPLIER: f64 = 100.0;
+
+
+/// Works out what a guess pays when it is paid by its odds: break-even for a guess that is
+/// right with the given chance is (1 - chance) / chance times the bet, and it wins
+/// ODDS_PAYOUT_SHARE of that, up to MAX_ODDS_MULTIPLIER.  So a 1 in 4 guess pays 2.85 times
+/// the bet, and a 3 in 4 guess about a third of it.
+///
+/// # Arguments
+///
+/// * `chance` - The chance of the guess being right, from 0.0 to 1.0.
+///
+pub fn odds_multiplier(chance: f64) -> f64 {
+    // A guess that can't be right never gets paid, so it is only kept from dividing by 0
+    if chance <= 0.0 {
+        return MAX_ODDS_MULTIPLIER;
+    }
+    ((1.0 - chance) / chance * ODDS_PAYOUT_SHARE).min(MAX_ODDS_MULTIPLIER)
+}
+
+// Pays a guess by its chance among chances, the letter of each guess with its chance of being
+// right.  A guess that isn't among them pays nothing
+fn pay_by_odds(chances: &[(char, f64)], guess: &str) -> f64 {
+    chances.iter().find(|(letter, _)| letter.to_string() == guess).map_or(0.0, |&(_, chance)| odds_multiplier(chance))
+}
+
+// Writes out what each guess pays, for variants that pay by the odds, e.g.
+// "A right guess pays (s) 11.40, (m) 3.21, or (l) 0.18 times your bet."
+fn describe_payouts(chances: &[(char, f64)]) -> String {
+    let payouts: Vec<String> = chances.iter().map(|&(letter, chance)| format!("({}) {:.2}", letter, odds_multiplier(chance))).collect();
+    format!("A right guess pays {} times your bet.", payouts.join(", "))
+}
+
+
+/// Puts a probability into words for the hints, so they give an idea of the odds
+/// without spelling out exact numbers.
+///
+/// # Arguments
+///
+/// * `chance` - The probability to describe, from 0.0 to 1.0.
+///
+fn likelihood(chance: f64) -> &'static str {
+    if chance <= 0.0 {
+        "impossible"
+    }
+    else if chance < 0.35 {
+        "unlikely"
+    }
+    else if chance <= 0.65 {
+        "about a coin flip"
+    }
+    else if chance < 1.0 {
+        "likely"
+    }
+    else {
+        "certain"
+    }
+}
+
+/// The words for (h)igher, (l)ower, and the (s)ame, which most variants' guesses are.
+pub const HIGHER_LOWER_SAME_WORDS: [(&str, char); 3] = [("higher", 'h'), ("lower", 'l'), ("same", 's')];
+
+// Different values is an enum which has two different values
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum DifferentValues {
+    FirstGeneratedVal,
+    SecondGeneratedVal,
+}
+
+impl DifferentValues {
+    /// Which of the two values this is, in words: "first" or "second".
+    pub fn ordinal(&self) -> &'static str {
+        match self {
+            DifferentValues::FirstGeneratedVal => "first",
+            DifferentValues::SecondGeneratedVal => "second",
+        }
+    }
+}
+
+
+/// GuppiesVariant is a trait,which are different variants of gameplay
+///
+/// # Methods
+/// 
+/// * 'generate_first' is a mutable reference that generates the first random value
+/// * 'generate_second' is a mutable reference that generates only the second random value,
+///   leaving the first value the user already saw untouched
+/// * both generate methods draw from the rng they are handed rather than thread_rng(), so a
+///   seeded rng (e.g. StdRng::seed_from_u64) makes a game reproducible
+/// * 'tell_random' tells the user the two randomly generated values, takes an enum as an argument
+///   that tells the user (prints out) one of the two generated values.  
+///   It takes an enum (variant) type that lets the method's caller choose whether to print 
+///   the first generated value or the second generated value.  It defaults to printing
+///   display_value, and variants override it to word it their own way or add details
+/// * 'display_value' is one of the two values written out the way the user sees it, e.g. a
+///   color's name or a Roman numeral, which can differ from the value the guess is checked
+///   against.  tell_random and anything that records a round (like the round log) show this
+/// * 'reset' puts the values back the way they were when the variant was made, keeping any
+///   settings (like PlainGuppies' range), so the same variant can be played in another game
+/// * 'get_guess' gets the guess from the user through the console and returns a string.
+///   Besides the variant's own guesses it also lets through "q" to quit and "?" for a hint.
+///   Most variants just hand their question to prompt_guess
+/// * 'min_guess_options' is the letters of the variant's own guesses, like ['h', 'l', 's'],
+///   leaving out "q" and "?" since every variant takes those
+/// * 'guess_words' is the words the variant's guesses can be spelled out as, with the letter
+///   each stands for, like ("higher", 'h').  It defaults to the HIGHER_LOWER_SAME_WORDS among
+///   min_guess_options, and variants whose letters mean something else list their own, so a
+///   word from one variant (like "small" for 's') isn't taken by another
+/// * 'prompt_guess' asks a question until the answer is one of the valid letters or the
+///   variant's words for them (or "q", "?", or "ev"), and returns it as its letter, so the
+///   validating lives in one place instead of in every variant's get_guess.  Past the round's
+///   deadline it returns "q" and marks the round as timed out
+/// * 'name' is the variant's name, like "PlainGuppies", as the menus list it
+/// * 'active_name' is the name of the variant actually being played this round.  It defaults
+///   to name, and only variants made of other variants (like Manyguppies) say otherwise
+/// * 'describe' is a paragraph explaining the variant's rules, printed once when a game starts
+/// * 'hint' gives a spoiler-free idea of how likely each guess is, based on the first value
+/// * 'guess_chances' is the exact chance of each guess being right when the first value is
+///   first_value, as shown by display_value.  It defaults to empty, for variants that can't
+///   say, and expected_value works from it
+/// * 'read_guess' is get_guess, except a "?" prints the hint and asks again instead of
+///   using up the turn, an "ev" prints the expected value of each guess (see the round's
+///   bet) the same way, and a "q" is only let through once the user confirms they want to
+///   quit (or the input runs out)
+/// * 'strategy_guess' is the guess a Strategy makes for the round, for run_game_with_strategy.
+///   It defaults to the strategy's letter for the variant's hint, and variants whose guesses
+///   are made of parts (like FusionGuppies) ask the strategy once per part
+/// * 'check_guess' takes a guess as an argument which is a string and returns Ok with a bool 
+///   depending on if the guess was correct or not, or GuppiesError::InvalidGuess if the guess
+///   isn't one the variant understands
+/// * 'allows_same' is whether "(s)ame" is one of the variant's guesses.  It defaults to true,
+///   and variants that can turn "same" off leave it out of their prompt and reject it
+/// * 'payout_multiplier' is how many times the bet a correct guess wins, so harder variants
+///   can pay more.  It defaults to 1.0, an even-money payout
+/// * 'same_multiplier' is how many times the bet a correct "s" guess wins instead, since the
+///   same is usually much rarer than higher or lower.  It defaults to SAME_MULTIPLIER, and
+///   variants where "s" isn't a rare guess pay their payout_multiplier for it
+/// * 'guess_multiplier' is how many times the bet a correct guess wins, for the guess that was
+///   made.  It defaults to same_multiplier for "s" and payout_multiplier for anything else,
+///   and variants whose guesses are far from equally likely pay each one by its odds (see
+///   odds_multiplier) instead
+/// * 'max_multiplier' is the most guess_multiplier can be, so a bet can be checked before the
+///   guess is known.  It defaults to the bigger of payout_multiplier and same_multiplier
+/// * 'play_round' plays one whole round after the bet is placed and says how it ended.
+///   run_game calls it once per round, then settles the bet from the RoundOutcome (using
+///   guess_multiplier on a win).  The default is the standard round: generate and tell the
+///   first value, get a guess, generate the second value, check the guess, tell the second value.
+///   Variants with a different shape of round (like ChainGuppies) override it.  The round's
+///   RoundContext is passed on to every guess prompt in it
+pub trait GuppiesVariant {
+    fn generate_first(&mut self, rng: &mut dyn RngCore);
+    fn generate_second(&mut self, rng: &mut dyn RngCore);
+    fn tell_random(&self, console: &mut Console, value: DifferentValues) {
+        console.print(&format!("This is the {} value {}", value.ordinal(), self.display_value(value)))
+    }
+    fn display_value(&self, which: DifferentValues) -> String;
+    fn reset(&mut self);
+    fn get_guess(&self, console: &mut Console, round: &RoundContext) -> String;
+    fn min_guess_options(&self) -> Vec<char>;
+    fn guess_words(&self) -> Vec<(&'static str, char)> {
+        let valid = self.min_guess_options();
+        HIGHER_LOWER_SAME_WORDS.into_iter().filter(|(_, letter)| valid.contains(letter)).collect()
+    }
+    fn prompt_guess(&self, console: &mut Console, round: &RoundContext, question: &str, valid: &[char]) -> String {
+        let words = self.guess_words();
+        let mut prompt = question.to_string();
+        loop {
+            // Running out of time ends the round the way quitting does, and the game sees from
+            // the round that it was the time running out
+            let time_up = msg(MsgKey::TooSlow, round.lang).red().to_string();
+            let Ok(guess) = read_guess_input(console, &prompt, round.balance_line.as_deref(), round.deadline.map(|deadline| (deadline, time_up.as_str()))) else {
+                round.timed_out.set(true);
+                return "q".to_string();
+            };
+            let guess = guess.to_lowercase();
+            if guess == "ev" {
+                return guess;
+            }
+            if let Some(letter) = normalize_guess(&guess, valid, &words) {
+                return letter.to_string();
+            }
+            prompt = format!("Invalid guess.  {}", question);
+        }
+    }
+    fn check_guess(&self, guess:&str) -> Result<bool, GuppiesError>;
+    fn name(&self) -> &'static str;
+    fn active_name(&self) -> &'static str {
+        self.name()
+    }
+    fn describe(&self) -> String;
+    fn hint(&self) -> String;
+    fn guess_chances(&self, _first_value: i32) -> Vec<(char, f64)> {
+        Vec::new()
+    }
+    fn read_guess(&self, console: &mut Console, round: &RoundContext) -> String {
+        loop {
+            let guess = self.get_guess(console, round);
+            match guess.as_str() {
+                "?" => console.print(&self.hint().cyan().to_string()),
+                // Only a first value shown as a plain number can be worked out from
+                "ev" => {
+                    let first_value = self.display_value(DifferentValues::FirstGeneratedVal).parse().ok();
+                    let line = match (round.bet, first_value) {
+                        (Some(bet), Some(first_value)) => describe_expected_value(self, first_value, bet, round.decimal_places),
+                        _ => "Expected values aren't worked out for this game.".to_string(),
+                    };
+                    console.print(&line.cyan().to_string());
+                }
+                // Running out of time can't be taken back
+                "q" if round.timed_out.get() => return guess,
+                // A "q" typed by mistake goes back to the guess instead of ending the game
+                "q" => {
+                    let sure = read_input(console, msg(MsgKey::ConfirmQuitPrompt, round.lang));
+                    if sure.is_none_or(|answer| answer.to_lowercase() == msg(MsgKey::YesAnswer, round.lang)) {
+                        return guess;
+                    }
+                }
+                _ => return guess,
+            }
+        }
+    }
+    fn strategy_guess(&self, strategy: &mut dyn Strategy) -> String {
+        strategy.choose_guess(&self.hint()).to_string()
+    }
+    fn allows_same(&self) -> bool {
+        true
+    }
+    fn payout_multiplier(&self) -> f64 {
+        1.0
+    }
+    fn same_multiplier(&self) -> f64 {
+        SAME_MULTIPLIER
+    }
+    fn guess_multiplier(&self, guess: &str) -> f64 {
+        if guess == "s" {
+            self.same_multiplier()
+        }
+        else {
+            self.payout_multiplier()
+        }
+    }
+    fn max_multiplier(&self) -> f64 {
+        self.payout_multiplier().max(self.same_multiplier())
+    }
+    fn play_round(&mut self, console: &mut Console, rng: &mut dyn RngCore, round: &RoundContext) -> RoundOutcome {
+        // Generates the first random value and tells the user that value
+        self.generate_first(rng);
+        self.tell_random(console, DifferentValues::FirstGeneratedVal);
+
+        // gets the guess 
+        let mut guess = self.read_guess(console, round);
+
+        if guess == "q" {
+            return RoundOutcome::Quit;
+        }
+        // Generates only the second random value, so the first value the user
+        // saw is the one check_guess compares against
+        self.generate_second(rng);
+
+        // Check the player's guess before the second value is shown.  A guess the variant
+        // can't judge is reported and asked for again instead of crashing the game.
+        let correct = loop {
+            match self.check_guess(&guess) {
+                Ok(correct) => break correct,
+                Err(error) => {
+                    console.print(&format!("{}  Try again...", error.to_string().red()));
+                    guess = self.read_guess(console, round);
+                    // The user can also quit instead of fixing their guess
+                    if guess == "q" {
+                        return RoundOutcome::Quit;
+                    }
+                }
+            }
+        };
+
+        // Tells the user the second value
+        self.tell_random(console, DifferentValues::SecondGeneratedVal);
+        RoundOutcome::Finished { correct, guess }
+    }
+}
+
+/// RoundContext is what the game knows about the round being played that the guess prompts
+/// can use, handed to play_round and on down to prompt_guess, so the console only does I/O.
+/// RoundContext::default() (nothing known) is what to use outside a game.
+///
+/// # Fields
+///
+/// * 'balance_line' is what typing "bal" at a guess prompt prints.  None means "bal" is just
+///   a guess
+/// * 'bet' is the bet the round is played for, which "ev" at a guess prompt works out expected
+///   values from.  None means there is no bet to work them out for
+/// * 'decimal_places' is how many decimal places the currency's amounts have, for showing them
+/// * 'lang' is the language the game is played in, for the prompts that ask a yes or no
+/// * 'deadline' is when every guess in the round has to be in by, or None for no time limit
+/// * 'timed_out' is set by prompt_guess when the deadline passes, which ends the round as if
+///   the user quit, so the game can tell the two apart
+#[derive(Clone, Debug, Default)]
+pub struct RoundContext {
+    pub balance_line: Option<String>,
+    pub bet: Option<i32>,
+    pub decimal_places: u32,
+    pub lang: Lang,
+    pub deadline: Option<Instant>,
+    pub timed_out: Cell<bool>,
+}
+
+/// RoundOutcome is how a round from GuppiesVariant::play_round ended
+///
+/// # Variants
+///
+/// * 'Quit' means the user quit during the round, so the bet isn't settled
+/// * 'Finished' means the round was played out, with whether the user won it and the guess
+///   that decided it
+pub enum RoundOutcome {
+    Quit,
+    Finished { correct: bool, guess: String },
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+    use super::*;
+
+    // Reads the number after "This is the <ordinal> value " out of what a round printed
+    fn shown_value(printed: &str, ordinal: &str) -> i32 {
+        let prefix = format!("This is the {} value ", ordinal);
+        let line = printed.lines().find(|line| line.starts_with(&prefix)).unwrap();
+        line[prefix.len()..].trim().parse().unwrap()
+    }
+
+    #[test]
+    fn the_first_value_shown_is_the_one_checked() {
+        for seed in 0..20 {
+            let mut input = Cursor::new("h\n");
+            let mut output = Vec::new();
+            let console = &mut Console { input: &mut input, output: &mut output, quiet: true };
+            let mut variant = PlainGuppies::new();
+            let outcome = variant.play_round(console, &mut StdRng::seed_from_u64(seed), &RoundContext::default());
+            let printed = String::from_utf8(output).unwrap();
+            let (first, second) = (shown_value(&printed, "first"), shown_value(&printed, "second"));
+            let RoundOutcome::Finished {correct, guess} = outcome else { panic!("the round was quit") };
+            assert_eq!(guess, "h");
+            assert_eq!(correct, second > first, "first {} second {}", first, second);
+        }
+    }
+
+    // The values a variant draws over some rounds, as display_value shows them
+    fn drawn_values(variant: &mut dyn GuppiesVariant, seed: u64) -> Vec<String> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut values = Vec::new();
+        for _ in 0..10 {
+            variant.generate_first(&mut rng);
+            variant.generate_second(&mut rng);
+            values.push(variant.display_value(DifferentValues::FirstGeneratedVal));
+            values.push(variant.display_value(DifferentValues::SecondGeneratedVal));
+        }
+        values
+    }
+
+    #[test]
+    fn a_seeded_rng_draws_the_same_values_every_time() {
+        for (name, make) in variant_registry() {
+            let values = drawn_values(make().as_mut(), 42);
+            assert_eq!(values, drawn_values(make().as_mut(), 42), "{} drew different values", name);
+        }
+        let values = drawn_values(&mut PlainGuppies::new(), 42);
+        assert_ne!(values, drawn_values(&mut PlainGuppies::new(), 43));
+    }
+
+    #[test]
+    fn every_variant_describes_its_rules() {
+        let keywords = [
+            ("PlainGuppies", "(h)igher"), ("RainbowGuppies", "green"), ("OddOrEvenGuppies", "odd"),
+            ("Manyguppies", "picked at random"), ("ChainGuppies", "chain"), ("DateBasedGuppies", "weekday"),
+            ("PrimeGuppies", "(p)rime"), ("ModularArithmeticGuppies", "modulus"), ("FibonacciGuppies", "Fibonacci"),
+            ("SumGuppies", "digit"), ("RomanNumeralGuppies", "Roman"), ("ComparatorGuppies", "distance apart"),
+            ("SquareRootGuppies", "square"), ("ColorDistanceGuppies", "RGB"), ("AlphabetGuppies", "alphabet"),
+            ("ProbabilityGuppies", "odds"), ("FusionGuppies", "both"), ("RunningTotalGuppies", "total"),
+            ("DiceGuppies", "dice"), ("RainbowNumberGuppies", "color"),
+        ];
+        for (name, make) in variant_registry() {
+            let description = make().describe();
+            assert!(description.starts_with(name), "{}", description);
+            let (_, keyword) = keywords.iter().find(|(variant, _)| *variant == name).unwrap();
+            assert!(description.contains(keyword), "{} doesn't mention {}: {}", name, keyword, description);
+        }
+    }
+
+    #[test]
+    fn prompt_guess_asks_again_until_the_guess_is_valid() {
+        // c is a guess in RainbowGuppies but not here, so it is rejected along with x
+        let mut input = Cursor::new("x\nc\nL\n");
+        let mut output = Vec::new();
+        let console = &mut Console { input: &mut input, output: &mut output, quiet: false };
+        let guess = PlainGuppies::new().prompt_guess(console, &RoundContext::default(), "Higher or lower?", &['h', 'l', 's']);
+        assert_eq!(guess, "l");
+        assert_eq!(String::from_utf8(output).unwrap().matches("Invalid guess.  Higher or lower?").count(), 2);
+    }
+
+    #[test]
+    fn the_registry_makes_every_variant_under_its_own_name() {
+        let registry = variant_registry();
+        // One for each variant module
+        assert_eq!(registry.len(), 20);
+        for (name, make) in &registry {
+            assert_eq!(make().name(), *name);
+        }
+        let mut names: Vec<&str> = registry.iter().map(|(name, _)| *name).collect();
+        names.sort();
+        names.dedup();
+        assert_eq!(names.len(), registry.len());
+    }
+}
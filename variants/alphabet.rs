@@ -0,0 +1,136 @@
+// alphabet.rs
+//
+// The AlphabetGuppies variant, guessing where the second letter falls in the alphabet.
+//
+
+use rand::{Rng, RngCore};  // for generating random numbers
+use crate::console::Console;
+use crate::error::GuppiesError;
+use super::{DifferentValues, GuppiesVariant, RoundContext, likelihood};
+
+
+// Both letters are drawn from a to z
+const FIRST_LETTER: char = 'a';
+const LAST_LETTER: char = 'z';
+
+
+// AlphabetGuppies draws two letters and asks whether the second one comes earlier or later
+// in the alphabet than the first
+pub struct AlphabetGuppies {
+    letter_one: char,
+    letter_two: char,
+}
+
+impl AlphabetGuppies {
+    /// Makes an AlphabetGuppies game.
+    pub fn new() -> AlphabetGuppies {
+        AlphabetGuppies {letter_one: FIRST_LETTER, letter_two: FIRST_LETTER}
+    }
+}
+
+impl Default for AlphabetGuppies {
+    fn default() -> AlphabetGuppies {
+        AlphabetGuppies::new()
+    }
+}
+
+// This struct implements GuppiesVariant
+impl GuppiesVariant for AlphabetGuppies {
+    fn generate_first(&mut self, rng: &mut dyn RngCore) {
+        self.letter_one = rng.gen_range(FIRST_LETTER..=LAST_LETTER);
+    }
+
+    fn generate_second(&mut self, rng: &mut dyn RngCore) {
+        // Only the second letter is drawn, so the first one stays what the user saw
+        self.letter_two = rng.gen_range(FIRST_LETTER..=LAST_LETTER);
+    }
+
+    fn tell_random(&self, console: &mut Console, value: DifferentValues) {
+        console.print(&format!("This is the {} letter {}", value.ordinal(), self.display_value(value)))
+    }
+
+    fn display_value(&self, which: DifferentValues) -> String {
+        match which {
+            DifferentValues::FirstGeneratedVal => self.letter_one.to_string(),
+            DifferentValues::SecondGeneratedVal => self.letter_two.to_string(),
+        }
+    }
+
+    fn reset(&mut self) {
+        *self = AlphabetGuppies::new();
+    }
+
+    // Gets the guess from the user and makes sure its not invalid
+    fn get_guess(&self, console: &mut Console, round: &RoundContext) -> String {
+        self.prompt_guess(console, round, "Does the second letter come (e)arlier, (l)ater, or is it the (s)ame?   [Or (q)uit, or ? for a hint.]", &self.min_guess_options())
+    }
+
+    fn min_guess_options(&self) -> Vec<char> {
+        vec!['e', 'l', 's']
+    }
+
+    fn guess_words(&self) -> Vec<(&'static str, char)> {
+        vec![("earlier", 'e'), ("later", 'l'), ("same", 's')]
+    }
+
+    // Letters are compared by where they are in the alphabet, which is the order of their chars
+    fn check_guess(&self, guess:&str) -> Result<bool, GuppiesError> {
+        let correct = match guess.to_lowercase().as_str() {
+            "e" => self.letter_two < self.letter_one,
+            "l" => self.letter_two > self.letter_one,
+            "s" => self.letter_two == self.letter_one,
+            _ => return Err(GuppiesError::InvalidGuess(guess.to_string()))
+        };
+        Ok(correct)
+    }
+
+    fn name(&self) -> &'static str {
+        "AlphabetGuppies"
+    }
+
+    fn describe(&self) -> String {
+        format!("AlphabetGuppies: two letters are drawn from {} to {}.  After seeing the first one, guess whether the second comes (e)arlier or (l)ater in the alphabet, or is the (s)ame letter.  A right guess wins your bet.",
+            FIRST_LETTER, LAST_LETTER)
+    }
+
+    // Counts the letters before and after the first one
+    fn hint(&self) -> String {
+        let total = (LAST_LETTER as u32 - FIRST_LETTER as u32 + 1) as f64;
+        let earlier = (self.letter_one as u32 - FIRST_LETTER as u32) as f64;
+        let later = (LAST_LETTER as u32 - self.letter_one as u32) as f64;
+        format!("Hint: earlier is {}, later is {}, the same is {}.",
+            likelihood(earlier / total), likelihood(later / total), likelihood(1.0 / total))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+    use super::*;
+
+    fn letters(letter_one: char, letter_two: char) -> AlphabetGuppies {
+        AlphabetGuppies {letter_one, letter_two}
+    }
+
+    #[test]
+    fn guesses_compare_places_in_the_alphabet() {
+        assert!(letters('c', 'f').check_guess("l").unwrap());
+        assert!(!letters('c', 'f').check_guess("e").unwrap());
+        assert!(letters('z', 'a').check_guess("e").unwrap());
+        assert!(letters('m', 'm').check_guess("s").unwrap());
+        assert!(!letters('m', 'm').check_guess("l").unwrap());
+    }
+
+    #[test]
+    fn only_lowercase_letters_are_drawn() {
+        let mut variant = AlphabetGuppies::new();
+        let mut rng = StdRng::seed_from_u64(70);
+        for _ in 0..100 {
+            variant.generate_first(&mut rng);
+            variant.generate_second(&mut rng);
+            assert!(variant.letter_one.is_ascii_lowercase() && variant.letter_two.is_ascii_lowercase());
+        }
+    }
+}
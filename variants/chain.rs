@@ -0,0 +1,166 @@
+// chain.rs
+//
+// The ChainGuppies variant, where one round keeps going until a wrong guess.
+//
+
+use rand::{Rng, RngCore};  // for generating random numbers
+use crate::console::Console;
+use crate::error::GuppiesError;
+use super::{DifferentValues, GuppiesVariant, RoundContext, RoundOutcome, likelihood};
+
+
+// ChainGuppies is like PlainGuppies, but one round keeps going: every correct higher/lower
+// guess reveals another number to guess against, until the user misses.  The chain length is
+// how many guesses in a row were right, and a chain of n pays n times the bet (0 loses the bet)
+pub struct ChainGuppies {
+    value_one: i32,
+    value_two: i32,
+    chain_length: u32,
+}
+
+impl ChainGuppies {
+    /// Makes a ChainGuppies game, with values from 1 to 10 inclusive.
+    pub fn new() -> ChainGuppies {
+        ChainGuppies {value_one: 1, value_two: 2, chain_length: 0}
+    }
+}
+
+impl Default for ChainGuppies {
+    fn default() -> ChainGuppies {
+        ChainGuppies::new()
+    }
+}
+
+impl GuppiesVariant for ChainGuppies {
+    fn generate_first(&mut self, rng: &mut dyn RngCore) {
+        self.value_one = rng.gen_range(1..11);
+    }
+
+    fn generate_second(&mut self, rng: &mut dyn RngCore) {
+        self.value_two = rng.gen_range(1..11);
+    }
+
+    fn tell_random(&self, console: &mut Console, value: DifferentValues) {
+        let which = match value {
+            DifferentValues::FirstGeneratedVal => "current",
+            DifferentValues::SecondGeneratedVal => "next",
+        };
+        console.print(&format!("This is the {} value {}", which, self.display_value(value)))
+    }
+
+    fn display_value(&self, which: DifferentValues) -> String {
+        match which {
+            DifferentValues::FirstGeneratedVal => self.value_one.to_string(),
+            DifferentValues::SecondGeneratedVal => self.value_two.to_string(),
+        }
+    }
+
+    // A finished chain is thrown away along with the values
+    fn reset(&mut self) {
+        *self = ChainGuppies::new();
+    }
+
+    // There is no (s)ame guess, a repeat number just ends the chain
+    fn get_guess(&self, console: &mut Console, round: &RoundContext) -> String {
+        self.prompt_guess(console, round, "Is the next number (h)igher or (l)ower?   [Or (q)uit, or ? for a hint.]", &self.min_guess_options())
+    }
+
+    fn min_guess_options(&self) -> Vec<char> {
+        vec!['h', 'l']
+    }
+
+    fn check_guess(&self, guess:&str) -> Result<bool, GuppiesError> {
+        let correct = match guess.to_lowercase().as_str() {
+            "h" => self.value_two > self.value_one,
+            "l" => self.value_two < self.value_one,
+            _ => return Err(GuppiesError::InvalidGuess(guess.to_string()))
+        };
+        Ok(correct)
+    }
+
+    fn name(&self) -> &'static str {
+        "ChainGuppies"
+    }
+
+    fn describe(&self) -> String {
+        "ChainGuppies: numbers are drawn from 1 to 10, and you keep guessing whether the next one is (h)igher or (l)ower than the last.  Every right guess adds to your chain, and the round ends on the first miss.  Your bet is paid once for every link in the chain, so a chain of 0 loses it.".to_string()
+    }
+
+    // Same as PlainGuppies over 1 to 10, except the same number counts as a miss
+    fn hint(&self) -> String {
+        let higher = (10 - self.value_one) as f64 / 10.0;
+        let lower = (self.value_one - 1) as f64 / 10.0;
+        format!("Hint: higher is {}, lower is {}.", likelihood(higher), likelihood(lower))
+    }
+
+    // The longer the chain, the bigger the payout
+    fn payout_multiplier(&self) -> f64 {
+        self.chain_length as f64
+    }
+
+    // A round is a whole chain: after each correct guess the next number becomes the one to
+    // beat, and the round only ends on a miss (or a quit, which throws the chain away)
+    fn play_round(&mut self, console: &mut Console, rng: &mut dyn RngCore, round: &RoundContext) -> RoundOutcome {
+        self.chain_length = 0;
+        self.generate_first(rng);
+        self.tell_random(console, DifferentValues::FirstGeneratedVal);
+
+        loop {
+            let guess = self.read_guess(console, round);
+            if guess == "q" {
+                return RoundOutcome::Quit;
+            }
+            self.generate_second(rng);
+            self.tell_random(console, DifferentValues::SecondGeneratedVal);
+
+            if matches!(self.check_guess(&guess), Ok(true)) {
+                self.chain_length += 1;
+                console.print(&format!("Right! Your chain is {} long.", self.chain_length));
+                self.value_one = self.value_two;
+            }
+            else {
+                console.print(&format!("The chain is broken after {}.", self.chain_length));
+                return RoundOutcome::Finished { correct: self.chain_length > 0, guess };
+            }
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+    use super::*;
+
+    // The guess that is right for the next value, or None if it repeats the current one
+    fn right_guess(current: i32, next: i32) -> Option<&'static str> {
+        match next.cmp(&current) {
+            std::cmp::Ordering::Greater => Some("h"),
+            std::cmp::Ordering::Less => Some("l"),
+            std::cmp::Ordering::Equal => None,
+        }
+    }
+
+    #[test]
+    fn two_right_guesses_then_a_wrong_one_is_a_chain_of_2() {
+        // Finds a seed whose first three numbers can be guessed right twice, by drawing them
+        // the way the round will
+        let (seed, values) = (0..).map(|seed| {
+            let mut rng = StdRng::seed_from_u64(seed);
+            (seed, [(); 4].map(|_| rng.gen_range(1..11)))
+        }).find(|(_, values)| values[1] != values[0] && values[2] != values[1]).unwrap();
+        let wrong = if right_guess(values[2], values[3]) == Some("h") { "l" } else { "h" };
+        let script = format!("{}\n{}\n{}\n", right_guess(values[0], values[1]).unwrap(), right_guess(values[1], values[2]).unwrap(), wrong);
+
+        let mut input = Cursor::new(script);
+        let mut output = Vec::new();
+        let console = &mut Console { input: &mut input, output: &mut output, quiet: true };
+        let mut variant = ChainGuppies::new();
+        let outcome = variant.play_round(console, &mut StdRng::seed_from_u64(seed), &RoundContext::default());
+        assert!(matches!(outcome, RoundOutcome::Finished {correct: true, ..}));
+        assert_eq!(variant.payout_multiplier(), 2.0);
+        assert!(String::from_utf8(output).unwrap().contains("The chain is broken after 2."));
+    }
+}
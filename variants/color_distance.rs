@@ -0,0 +1,181 @@
+// color_distance.rs
+//
+// The ColorDistanceGuppies variant, comparing how close two colors are to green by their RGB values.
+//
+
+use rand::{Rng, RngCore};  // for generating random numbers
+use crate::console::Console;
+use crate::error::GuppiesError;
+use super::{DifferentValues, GuppiesVariant, RoundContext, likelihood};
+
+
+// The colors that can be drawn, with their red, green, and blue values
+const NAMED_COLORS: [(&str, (u8, u8, u8)); 12] = [
+    ("Red", (255, 0, 0)),
+    ("Orange", (255, 165, 0)),
+    ("Yellow", (255, 255, 0)),
+    ("Lime", (50, 205, 50)),
+    ("Forest Green", (34, 139, 34)),
+    ("Olive", (128, 128, 0)),
+    ("Teal", (0, 128, 128)),
+    ("Cyan", (0, 255, 255)),
+    ("Blue", (0, 0, 255)),
+    ("Purple", (128, 0, 128)),
+    ("White", (255, 255, 255)),
+    ("Black", (0, 0, 0)),
+];
+
+// Pure green, which every color is measured against
+const PURE_GREEN: (u8, u8, u8) = (0, 255, 0);
+
+
+/// Measures how far apart two colors are as points in RGB space, the straight-line
+/// (Euclidean) distance between their red, green, and blue values.
+///
+/// # Arguments
+///
+/// * `a` - One color's red, green, and blue values.
+/// * `b` - The other color's red, green, and blue values.
+///
+fn rgb_distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> f64 {
+    let red = a.0 as f64 - b.0 as f64;
+    let green = a.1 as f64 - b.1 as f64;
+    let blue = a.2 as f64 - b.2 as f64;
+    (red * red + green * green + blue * blue).sqrt()
+}
+
+// How far the color at index is from pure green
+fn distance_to_green(index: usize) -> f64 {
+    rgb_distance(NAMED_COLORS[index].1, PURE_GREEN)
+}
+
+
+// ColorDistanceGuppies is like RainbowGuppies, but "closer to green" is measured by the colors'
+// RGB values instead of their place in the rainbow.  The colors are kept as indexes into NAMED_COLORS
+pub struct ColorDistanceGuppies {
+    color_one: usize,
+    color_two: usize,
+}
+
+impl ColorDistanceGuppies {
+    /// Makes a ColorDistanceGuppies game.
+    pub fn new() -> ColorDistanceGuppies {
+        ColorDistanceGuppies {color_one: 0, color_two: 0}
+    }
+}
+
+impl Default for ColorDistanceGuppies {
+    fn default() -> ColorDistanceGuppies {
+        ColorDistanceGuppies::new()
+    }
+}
+
+impl GuppiesVariant for ColorDistanceGuppies {
+    fn generate_first(&mut self, rng: &mut dyn RngCore) {
+        self.color_one = rng.gen_range(0..NAMED_COLORS.len());
+    }
+
+    fn generate_second(&mut self, rng: &mut dyn RngCore) {
+        // Only the second color is drawn, so the first one stays what the user saw
+        self.color_two = rng.gen_range(0..NAMED_COLORS.len());
+    }
+
+    // The RGB values are shown along with the name, since they're what the distance is measured on
+    fn tell_random(&self, console: &mut Console, value: DifferentValues) {
+        let index = match value {
+            DifferentValues::FirstGeneratedVal => self.color_one,
+            DifferentValues::SecondGeneratedVal => self.color_two,
+        };
+        let (red, green, blue) = NAMED_COLORS[index].1;
+        console.print(&format!("This is the {} color {} (RGB {}, {}, {})", value.ordinal(), self.display_value(value), red, green, blue))
+    }
+
+    fn display_value(&self, which: DifferentValues) -> String {
+        match which {
+            DifferentValues::FirstGeneratedVal => NAMED_COLORS[self.color_one].0.to_string(),
+            DifferentValues::SecondGeneratedVal => NAMED_COLORS[self.color_two].0.to_string(),
+        }
+    }
+
+    fn reset(&mut self) {
+        *self = ColorDistanceGuppies::new();
+    }
+
+    // Gets the guess from the user and makes sure its not invalid
+    fn get_guess(&self, console: &mut Console, round: &RoundContext) -> String {
+        self.prompt_guess(console, round, "Is the second color (c)loser to pure green, (f)arther, or the (s)ame distance?   [Or (q)uit, or ? for a hint.]", &self.min_guess_options())
+    }
+
+    fn min_guess_options(&self) -> Vec<char> {
+        vec!['c', 'f', 's']
+    }
+
+    fn guess_words(&self) -> Vec<(&'static str, char)> {
+        vec![("closer", 'c'), ("farther", 'f'), ("same", 's')]
+    }
+
+    // Compares the two colors' RGB distances to pure green
+    fn check_guess(&self, guess:&str) -> Result<bool, GuppiesError> {
+        let distance_first = distance_to_green(self.color_one);
+        let distance_second = distance_to_green(self.color_two);
+        let correct = match guess.to_lowercase().as_str() {
+            "c" => distance_second < distance_first,
+            "f" => distance_second > distance_first,
+            "s" => distance_second == distance_first,
+            _ => return Err(GuppiesError::InvalidGuess(guess.to_string()))
+        };
+        Ok(correct)
+    }
+
+    fn name(&self) -> &'static str {
+        "ColorDistanceGuppies"
+    }
+
+    fn describe(&self) -> String {
+        let names: Vec<&str> = NAMED_COLORS.iter().map(|(name, _)| *name).collect();
+        format!("ColorDistanceGuppies: two colors are drawn from {}.  After seeing the first one and its RGB values, guess whether the second is (c)loser to pure green (RGB 0, 255, 0), (f)arther from it, or the (s)ame distance, measured as a straight line between the RGB values.  A right guess wins twice your bet.", names.join(", "))
+    }
+
+    // Counts how many colors are closer to and farther from green than the first color
+    fn hint(&self) -> String {
+        let distance_first = distance_to_green(self.color_one);
+        let total = NAMED_COLORS.len() as f64;
+        let closer = (0..NAMED_COLORS.len()).filter(|&index| distance_to_green(index) < distance_first).count() as f64 / total;
+        let farther = (0..NAMED_COLORS.len()).filter(|&index| distance_to_green(index) > distance_first).count() as f64 / total;
+        format!("Hint: closer is {}, farther is {}, the same is {}.", likelihood(closer), likelihood(farther), likelihood(1.0 - closer - farther))
+    }
+
+    // Like RainbowGuppies, distances to green are hard to eyeball, so it pays double
+    fn payout_multiplier(&self) -> f64 {
+        2.0
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The index of a named color
+    fn color(name: &str) -> usize {
+        NAMED_COLORS.iter().position(|(color, _)| *color == name).unwrap()
+    }
+
+    #[test]
+    fn distances_to_green() {
+        assert_eq!(rgb_distance(PURE_GREEN, PURE_GREEN), 0.0);
+        assert_eq!(distance_to_green(color("Yellow")), 255.0);
+        assert!((distance_to_green(color("Lime")) - 50.0 * 3f64.sqrt()).abs() < 1e-9);
+        assert!(distance_to_green(color("Lime")) < distance_to_green(color("Forest Green")));
+        assert!(distance_to_green(color("Blue")) > distance_to_green(color("Cyan")));
+    }
+
+    #[test]
+    fn guesses_follow_the_rgb_distances() {
+        let variant = |first, second| ColorDistanceGuppies {color_one: color(first), color_two: color(second)};
+        assert!(variant("Blue", "Lime").check_guess("c").unwrap());
+        assert!(variant("Lime", "Red").check_guess("f").unwrap());
+        // Yellow and Cyan are both 255 from green
+        assert!(variant("Yellow", "Cyan").check_guess("s").unwrap());
+    }
+}
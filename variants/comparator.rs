@@ -0,0 +1,198 @@
+// comparator.rs
+//
+// The ComparatorGuppies variant, guessing how far apart the two numbers are.
+//
+
+use rand::{Rng, RngCore};  // for generating random numbers
+use crate::console::Console;
+use crate::error::GuppiesError;
+use super::{DifferentValues, GuppiesVariant, MAX_ODDS_MULTIPLIER, RoundContext, describe_payouts, likelihood, pay_by_odds};
+
+
+// Both numbers are drawn from 1 to 100
+const COMPARATOR_MIN: i32 = 1;
+const COMPARATOR_MAX: i32 = 100;
+
+// A difference up to SMALL_MAX is small, up to MEDIUM_MAX is medium, and anything bigger is large
+const SMALL_MAX: i32 = 3;
+const MEDIUM_MAX: i32 = 7;
+
+
+/// Sorts the difference between two numbers into the bucket a guess names: "s" for small
+/// (0 to 3 apart), "m" for medium (4 to 7 apart), or "l" for large (8 or more apart).
+///
+/// # Arguments
+///
+/// * `a` - One of the numbers.
+/// * `b` - The other number.
+///
+fn difference_bucket(a: i32, b: i32) -> &'static str {
+    let difference = (a - b).abs();
+    if difference <= SMALL_MAX {
+        "s"
+    }
+    else if difference <= MEDIUM_MAX {
+        "m"
+    }
+    else {
+        "l"
+    }
+}
+
+/// Works out the chance of each bucket, given the first number, by counting how many of the
+/// possible second numbers land in it.  A first number near either end has fewer neighbours,
+/// so small and medium are a bit less likely there.
+///
+/// # Arguments
+///
+/// * `first` - The first number.
+///
+fn bucket_chances(first: i32) -> Vec<(char, f64)> {
+    let total = (COMPARATOR_MAX - COMPARATOR_MIN + 1) as f64;
+    ['s', 'm', 'l'].iter().map(|&letter| {
+        let bucket = letter.to_string();
+        let count = (COMPARATOR_MIN..=COMPARATOR_MAX).filter(|&n| difference_bucket(first, n) == bucket).count();
+        (letter, count as f64 / total)
+    }).collect()
+}
+
+
+// ComparatorGuppies draws two numbers from 1 to 100 and asks whether they will be a small,
+// medium, or large distance apart, instead of which one is bigger
+pub struct ComparatorGuppies {
+    num_one: i32,
+    num_two: i32,
+}
+
+impl ComparatorGuppies {
+    /// Makes a ComparatorGuppies game.
+    pub fn new() -> ComparatorGuppies {
+        ComparatorGuppies {num_one: COMPARATOR_MIN, num_two: COMPARATOR_MIN}
+    }
+}
+
+impl Default for ComparatorGuppies {
+    fn default() -> ComparatorGuppies {
+        ComparatorGuppies::new()
+    }
+}
+
+// This struct implements GuppiesVariant
+impl GuppiesVariant for ComparatorGuppies {
+    fn generate_first(&mut self, rng: &mut dyn RngCore) {
+        self.num_one = rng.gen_range(COMPARATOR_MIN..COMPARATOR_MAX + 1);
+    }
+
+    fn generate_second(&mut self, rng: &mut dyn RngCore) {
+        // Only the second number is rolled, so the first one stays what the user saw
+        self.num_two = rng.gen_range(COMPARATOR_MIN..COMPARATOR_MAX + 1);
+    }
+
+    fn tell_random(&self, console: &mut Console, value: DifferentValues) {
+        let shown = format!("This is the {} number {}", value.ordinal(), self.display_value(value));
+        match value {
+            // Each guess is paid by its odds, which depend on the first number, so they're shown with it
+            DifferentValues::FirstGeneratedVal => console.print(&format!("{}\n{}", shown, describe_payouts(&bucket_chances(self.num_one)))),
+            DifferentValues::SecondGeneratedVal => {
+                console.print(&format!("{} ({} apart)", shown, (self.num_one - self.num_two).abs()))
+            }
+        }
+    }
+
+    fn display_value(&self, which: DifferentValues) -> String {
+        match which {
+            DifferentValues::FirstGeneratedVal => self.num_one.to_string(),
+            DifferentValues::SecondGeneratedVal => self.num_two.to_string(),
+        }
+    }
+
+    fn reset(&mut self) {
+        *self = ComparatorGuppies::new();
+    }
+
+    // Gets the guess from the user and makes sure its not invalid
+    fn get_guess(&self, console: &mut Console, round: &RoundContext) -> String {
+        self.prompt_guess(console, round, "Is the difference (s)mall (0-3), (m)edium (4-7), or (l)arge (8+)?   [Or (q)uit, or ? for a hint.]", &self.min_guess_options())
+    }
+
+    fn min_guess_options(&self) -> Vec<char> {
+        vec!['s', 'm', 'l']
+    }
+
+    fn guess_words(&self) -> Vec<(&'static str, char)> {
+        vec![("small", 's'), ("medium", 'm'), ("large", 'l')]
+    }
+
+    // Checks the users guess against the bucket the difference falls in
+    fn check_guess(&self, guess:&str) -> Result<bool, GuppiesError> {
+        let guess = guess.to_lowercase();
+        match guess.as_str() {
+            "s" | "m" | "l" => Ok(difference_bucket(self.num_one, self.num_two) == guess),
+            _ => Err(GuppiesError::InvalidGuess(guess.to_string()))
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "ComparatorGuppies"
+    }
+
+    fn describe(&self) -> String {
+        format!("ComparatorGuppies: two numbers are drawn from {} to {}.  After seeing the first one, guess whether the two will be a (s)mall (0 to {}), (m)edium ({} to {}), or (l)arge ({} or more) distance apart.  Large is much more likely than the others, so each guess is paid by its odds, which are shown with the first number.",
+            COMPARATOR_MIN, COMPARATOR_MAX, SMALL_MAX, SMALL_MAX + 1, MEDIUM_MAX, MEDIUM_MAX + 1)
+    }
+
+    fn hint(&self) -> String {
+        let chances = bucket_chances(self.num_one);
+        format!("Hint: small is {}, medium is {}, large is {}.", likelihood(chances[0].1), likelihood(chances[1].1), likelihood(chances[2].1))
+    }
+
+    fn guess_chances(&self, first_value: i32) -> Vec<(char, f64)> {
+        bucket_chances(first_value)
+    }
+
+    // Large is right about 85% of the time, so paying every guess the same would make always
+    // guessing it a winning strategy.  Each guess is paid by its odds instead
+    fn guess_multiplier(&self, guess: &str) -> f64 {
+        pay_by_odds(&bucket_chances(self.num_one), guess)
+    }
+
+    // Here (s) is for small, not the same, so it has no bigger payout of its own
+    fn same_multiplier(&self) -> f64 {
+        self.payout_multiplier()
+    }
+
+    fn max_multiplier(&self) -> f64 {
+        MAX_ODDS_MULTIPLIER
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn differences_are_bucketed_at_the_boundaries() {
+        assert_eq!(difference_bucket(50, 53), "s");
+        assert_eq!(difference_bucket(50, 46), "m");
+        assert_eq!(difference_bucket(50, 57), "m");
+        assert_eq!(difference_bucket(58, 50), "l");
+    }
+
+    #[test]
+    fn a_guess_is_right_when_its_bucket_is() {
+        let variant = ComparatorGuppies {num_one: 10, num_two: 17};
+        assert!(variant.check_guess("m").unwrap());
+        assert!(!variant.check_guess("s").unwrap());
+        assert!(!variant.check_guess("l").unwrap());
+    }
+
+    #[test]
+    fn the_unlikely_buckets_pay_more() {
+        let variant = ComparatorGuppies {num_one: 50, num_two: 50};
+        let (small, medium, large) = (variant.guess_multiplier("s"), variant.guess_multiplier("m"), variant.guess_multiplier("l"));
+        // 7 of the 100 numbers are small, 8 are medium, and 85 are large
+        assert!((small - 0.93 / 0.07 * 0.95).abs() < 1e-9);
+        assert!(small > medium && medium > 1.0 && large < 1.0);
+    }
+}
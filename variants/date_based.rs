@@ -0,0 +1,162 @@
+// date_based.rs
+//
+// The DateBasedGuppies variant, comparing the weekdays of two dates.
+//
+
+use rand::{Rng, RngCore};  // for generating random numbers
+use crate::console::Console;
+use crate::error::GuppiesError;
+use super::{DifferentValues, GuppiesVariant, RoundContext, likelihood};
+
+
+// The dates in DateBasedGuppies are days of 2023, which isn't a leap year and starts on a Sunday
+const WEEKDAYS: [&str; 7] = ["Monday", "Tuesday", "Wednesday", "Thursday", "Friday", "Saturday", "Sunday"];
+const MONTHS: [(&str, i32); 12] = [
+    ("January", 31), ("February", 28), ("March", 31), ("April", 30), ("May", 31), ("June", 30),
+    ("July", 31), ("August", 31), ("September", 30), ("October", 31), ("November", 30), ("December", 31),
+];
+const DAYS_IN_YEAR: i32 = 365;
+// Index into WEEKDAYS of January 1st (a Sunday)
+const NEW_YEARS_WEEKDAY: i32 = 6;
+
+/// Finds the weekday of a day of the year, as an index into WEEKDAYS (0 is Monday).
+///
+/// # Arguments
+///
+/// * `day_of_year` - The day of the year, 1 being January 1st.
+///
+fn weekday(day_of_year: i32) -> i32 {
+    (day_of_year - 1 + NEW_YEARS_WEEKDAY) % 7
+}
+
+/// Turns a day of the year into a readable date like "Tuesday, March 14".
+///
+/// # Arguments
+///
+/// * `day_of_year` - The day of the year, 1 being January 1st.
+///
+fn format_date(day_of_year: i32) -> String {
+    let mut day = day_of_year;
+    for (month, length) in MONTHS {
+        if day <= length {
+            return format!("{}, {} {}", WEEKDAYS[weekday(day_of_year) as usize], month, day);
+        }
+        day -= length;
+    }
+    panic!("{} isn't a day of the year", day_of_year)
+}
+
+// DateBasedGuppies picks two random dates and asks if the second one falls (e)arlier, (l)ater,
+// or on the (s)ame weekday as the first, with weeks running Monday to Sunday
+pub struct DateBasedGuppies {
+    day_one: i32,
+    day_two: i32,
+}
+
+impl DateBasedGuppies {
+    /// Makes a DateBasedGuppies game.
+    pub fn new() -> DateBasedGuppies {
+        DateBasedGuppies {day_one: 1, day_two: 1}
+    }
+}
+
+impl Default for DateBasedGuppies {
+    fn default() -> DateBasedGuppies {
+        DateBasedGuppies::new()
+    }
+}
+
+impl GuppiesVariant for DateBasedGuppies {
+    fn generate_first(&mut self, rng: &mut dyn RngCore) {
+        self.day_one = rng.gen_range(1..DAYS_IN_YEAR + 1);
+    }
+
+    fn generate_second(&mut self, rng: &mut dyn RngCore) {
+        self.day_two = rng.gen_range(1..DAYS_IN_YEAR + 1);
+    }
+
+    fn tell_random(&self, console: &mut Console, value: DifferentValues) {
+        console.print(&format!("This is the {} date {}", value.ordinal(), self.display_value(value)))
+    }
+
+    fn display_value(&self, which: DifferentValues) -> String {
+        match which {
+            DifferentValues::FirstGeneratedVal => format_date(self.day_one),
+            DifferentValues::SecondGeneratedVal => format_date(self.day_two),
+        }
+    }
+
+    fn reset(&mut self) {
+        *self = DateBasedGuppies::new();
+    }
+
+    fn get_guess(&self, console: &mut Console, round: &RoundContext) -> String {
+        self.prompt_guess(console, round, "Is the second date on an (e)arlier, (l)ater, or the (s)ame weekday?   [Or (q)uit, or ? for a hint.]", &self.min_guess_options())
+    }
+
+    fn min_guess_options(&self) -> Vec<char> {
+        vec!['e', 'l', 's']
+    }
+
+    fn guess_words(&self) -> Vec<(&'static str, char)> {
+        vec![("earlier", 'e'), ("later", 'l'), ("same", 's')]
+    }
+
+    // Only the weekdays are compared, not the dates themselves
+    fn check_guess(&self, guess:&str) -> Result<bool, GuppiesError> {
+        let weekday_one = weekday(self.day_one);
+        let weekday_two = weekday(self.day_two);
+        let correct = match guess.to_lowercase().as_str() {
+            "e" => weekday_two < weekday_one,
+            "l" => weekday_two > weekday_one,
+            "s" => weekday_two == weekday_one,
+            _ => return Err(GuppiesError::InvalidGuess(guess.to_string()))
+        };
+        Ok(correct)
+    }
+
+    fn name(&self) -> &'static str {
+        "DateBasedGuppies"
+    }
+
+    fn describe(&self) -> String {
+        "DateBasedGuppies: two dates are drawn from 2023.  After seeing the first one, guess whether the second falls on an (e)arlier, (l)ater, or the (s)ame weekday, counting the week from Monday to Sunday.  A right guess wins your bet.".to_string()
+    }
+
+    // Every weekday is about equally likely, so it comes down to where the first one falls
+    fn hint(&self) -> String {
+        let first = weekday(self.day_one) as f64;
+        format!("Hint: earlier is {}, later is {}, the same is {}.",
+            likelihood(first / 7.0), likelihood((6.0 - first) / 7.0), likelihood(1.0 / 7.0))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dates_are_written_with_their_weekday() {
+        assert_eq!(format_date(1), "Sunday, January 1");
+        assert_eq!(format_date(73), "Tuesday, March 14");
+        assert_eq!(format_date(365), "Sunday, December 31");
+    }
+
+    #[test]
+    fn only_the_weekdays_are_compared() {
+        // Sunday, January 1 and Monday, January 2: Monday starts the week
+        let variant = DateBasedGuppies {day_one: 1, day_two: 2};
+        assert!(variant.check_guess("e").unwrap());
+        assert!(!variant.check_guess("l").unwrap());
+
+        // Monday, January 2 and Friday, January 6
+        let variant = DateBasedGuppies {day_one: 2, day_two: 6};
+        assert!(variant.check_guess("l").unwrap());
+
+        // Sunday, January 1 and Sunday, January 8
+        let variant = DateBasedGuppies {day_one: 1, day_two: 8};
+        assert!(variant.check_guess("s").unwrap());
+        assert!(!variant.check_guess("e").unwrap());
+    }
+}
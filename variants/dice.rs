@@ -0,0 +1,153 @@
+// dice.rs
+//
+// The DiceGuppies variant, comparing the sums of two rolls of two dice.
+//
+
+use rand::{Rng, RngCore};  // for generating random numbers
+use crate::console::Console;
+use crate::error::GuppiesError;
+use super::{DifferentValues, GuppiesVariant, RoundContext, likelihood};
+
+
+// The faces of a die
+const DIE_SIDES: i32 = 6;
+
+// Rolls one die
+fn roll_die(rng: &mut dyn RngCore) -> i32 {
+    rng.gen_range(1..DIE_SIDES + 1)
+}
+
+
+// DiceGuppies rolls two dice, then two more, and the guess is how the second pair's sum
+// compares to the first pair's.  die_one and die_two are the first roll, die_three and
+// die_four the second
+pub struct DiceGuppies {
+    die_one: i32,
+    die_two: i32,
+    die_three: i32,
+    die_four: i32,
+}
+
+impl DiceGuppies {
+    /// Makes a DiceGuppies game, rolling two six-sided dice at a time.
+    pub fn new() -> DiceGuppies {
+        DiceGuppies {die_one: 1, die_two: 1, die_three: 1, die_four: 1}
+    }
+
+    fn first_sum(&self) -> i32 {
+        self.die_one + self.die_two
+    }
+
+    fn second_sum(&self) -> i32 {
+        self.die_three + self.die_four
+    }
+}
+
+impl Default for DiceGuppies {
+    fn default() -> DiceGuppies {
+        DiceGuppies::new()
+    }
+}
+
+impl GuppiesVariant for DiceGuppies {
+    fn generate_first(&mut self, rng: &mut dyn RngCore) {
+        self.die_one = roll_die(rng);
+        self.die_two = roll_die(rng);
+    }
+
+    fn generate_second(&mut self, rng: &mut dyn RngCore) {
+        // Only the second pair is rolled, so the first pair stays what the user saw
+        self.die_three = roll_die(rng);
+        self.die_four = roll_die(rng);
+    }
+
+    fn tell_random(&self, console: &mut Console, value: DifferentValues) {
+        console.print(&format!("This is the {} roll {}", value.ordinal(), self.display_value(value)))
+    }
+
+    // Both dice are shown along with their sum, since the sum is what the guess is about
+    fn display_value(&self, which: DifferentValues) -> String {
+        match which {
+            DifferentValues::FirstGeneratedVal => format!("{} and {} (a sum of {})", self.die_one, self.die_two, self.first_sum()),
+            DifferentValues::SecondGeneratedVal => format!("{} and {} (a sum of {})", self.die_three, self.die_four, self.second_sum()),
+        }
+    }
+
+    fn reset(&mut self) {
+        *self = DiceGuppies::new();
+    }
+
+    fn get_guess(&self, console: &mut Console, round: &RoundContext) -> String {
+        self.prompt_guess(console, round, "Will the second roll's sum be (h)igher, (l)ower, or the (s)ame?   [Or (q)uit, or ? for a hint.]", &self.min_guess_options())
+    }
+
+    fn min_guess_options(&self) -> Vec<char> {
+        vec!['h', 'l', 's']
+    }
+
+    fn check_guess(&self, guess:&str) -> Result<bool, GuppiesError> {
+        let correct = match guess.to_lowercase().as_str() {
+            "h" => self.second_sum() > self.first_sum(),
+            "l" => self.second_sum() < self.first_sum(),
+            "s" => self.second_sum() == self.first_sum(),
+            _ => return Err(GuppiesError::InvalidGuess(guess.to_string()))
+        };
+        Ok(correct)
+    }
+
+    fn name(&self) -> &'static str {
+        "DiceGuppies"
+    }
+
+    fn describe(&self) -> String {
+        "DiceGuppies: two dice are rolled, and then two more.  After seeing the first roll, guess whether the second roll's sum is (h)igher, (l)ower, or the (s)ame as the first's.  Sums in the middle, like 7, come up the most.  A right guess wins your bet.".to_string()
+    }
+
+    // Counts the 36 ways two dice can land that sum higher, lower, or the same as the first roll
+    fn hint(&self) -> String {
+        let sums: Vec<i32> = (1..DIE_SIDES + 1).flat_map(|one| (1..DIE_SIDES + 1).map(move |two| one + two)).collect();
+        let total = sums.len() as f64;
+        let higher = sums.iter().filter(|&&sum| sum > self.first_sum()).count() as f64 / total;
+        let lower = sums.iter().filter(|&&sum| sum < self.first_sum()).count() as f64 / total;
+        let same = sums.iter().filter(|&&sum| sum == self.first_sum()).count() as f64 / total;
+        format!("Hint: higher is {}, lower is {}, the same is {}.", likelihood(higher), likelihood(lower), likelihood(same))
+    }
+
+    // Sums in the middle come up often enough (a 7 one time in six) that the same can't pay
+    // extra without being a winning bet, so it pays like any other guess
+    fn same_multiplier(&self) -> f64 {
+        self.payout_multiplier()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dice(die_one: i32, die_two: i32, die_three: i32, die_four: i32) -> DiceGuppies {
+        DiceGuppies {die_one, die_two, die_three, die_four}
+    }
+
+    #[test]
+    fn the_sums_are_compared_not_the_dice() {
+        // 1 and 6 against 3 and 3 is 7 against 6, even though both of the second dice beat the 1
+        assert!(dice(1, 6, 3, 3).check_guess("l").unwrap());
+        assert!(dice(2, 2, 1, 4).check_guess("h").unwrap());
+        assert!(dice(5, 2, 3, 4).check_guess("s").unwrap());
+        assert!(!dice(5, 2, 3, 4).check_guess("h").unwrap());
+    }
+
+    #[test]
+    fn both_dice_are_shown() {
+        let variant = dice(2, 5, 6, 6);
+        let mut input = std::io::empty();
+        let mut output = Vec::new();
+        let mut console = Console { input: &mut input, output: &mut output, quiet: false };
+        variant.tell_random(&mut console, DifferentValues::FirstGeneratedVal);
+        variant.tell_random(&mut console, DifferentValues::SecondGeneratedVal);
+        let printed = String::from_utf8(output).unwrap();
+        assert!(printed.contains("This is the first roll 2 and 5 (a sum of 7)"));
+        assert!(printed.contains("This is the second roll 6 and 6 (a sum of 12)"));
+    }
+}
@@ -0,0 +1,145 @@
+// fibonacci.rs
+//
+// The FibonacciGuppies variant, comparing a number to the nearest Fibonacci number.
+//
+
+use rand::{Rng, RngCore};  // for generating random numbers
+use crate::console::Console;
+use crate::error::GuppiesError;
+use super::{DifferentValues, GuppiesVariant, RoundContext, likelihood};
+
+
+/// Finds the Fibonacci number (1, 2, 3, 5, 8, 13, ...) closest to n.  When n is exactly
+/// halfway between two of them, like 4 between 3 and 5, the smaller one wins.
+///
+/// # Arguments
+///
+/// * `n` - The number to find the nearest Fibonacci number to.
+///
+/// # Returns
+///
+/// * i32: the nearest Fibonacci number, which is 1 for anything below 1.
+///
+fn nearest_fib(n: i32) -> i32 {
+    let (mut smaller, mut larger) = (1, 2);
+    while larger < n {
+        (smaller, larger) = (larger, smaller + larger);
+    }
+    if n <= smaller || n - smaller <= larger - n {
+        smaller
+    }
+    else {
+        larger
+    }
+}
+
+
+// FibonacciGuppies draws a number from 1 to 100 and snaps it to the nearest Fibonacci number,
+// which is the value the user sees.  The second number is drawn from 1 to 100 as it is, and
+// the user guesses whether it is higher, lower, or the same as that Fibonacci number.
+pub struct FibonacciGuppies {
+    fib_one: i32,
+    num_two: i32,
+}
+
+impl FibonacciGuppies {
+    /// Makes a FibonacciGuppies game.
+    pub fn new() -> FibonacciGuppies {
+        FibonacciGuppies {fib_one: 1, num_two: 1}
+    }
+}
+
+impl Default for FibonacciGuppies {
+    fn default() -> FibonacciGuppies {
+        FibonacciGuppies::new()
+    }
+}
+
+// This struct implements GuppiesVariant
+impl GuppiesVariant for FibonacciGuppies {
+    fn generate_first(&mut self, rng: &mut dyn RngCore) {
+        // Only the Fibonacci number is kept, since that's what the second number is compared to
+        self.fib_one = nearest_fib(rng.gen_range(1..101));
+    }
+
+    fn generate_second(&mut self, rng: &mut dyn RngCore) {
+        self.num_two = rng.gen_range(1..101);
+    }
+
+    fn tell_random(&self, console: &mut Console, value: DifferentValues) {
+        let which = match value {
+            DifferentValues::FirstGeneratedVal => "Fibonacci",
+            DifferentValues::SecondGeneratedVal => "second",
+        };
+        console.print(&format!("This is the {} number {}", which, self.display_value(value)))
+    }
+
+    fn display_value(&self, which: DifferentValues) -> String {
+        match which {
+            DifferentValues::FirstGeneratedVal => self.fib_one.to_string(),
+            DifferentValues::SecondGeneratedVal => self.num_two.to_string(),
+        }
+    }
+
+    fn reset(&mut self) {
+        *self = FibonacciGuppies::new();
+    }
+
+    // Gets the guess from the user and makes sure its not invalid
+    fn get_guess(&self, console: &mut Console, round: &RoundContext) -> String {
+        self.prompt_guess(console, round, "Is the second number (h)igher, (l)ower, or the (s)ame as the Fibonacci number?   [Or (q)uit, or ? for a hint.]", &self.min_guess_options())
+    }
+
+    fn min_guess_options(&self) -> Vec<char> {
+        vec!['h', 'l', 's']
+    }
+
+    // Compares the second number to the Fibonacci number the user was shown
+    fn check_guess(&self, guess:&str) -> Result<bool, GuppiesError> {
+        let correct = match guess.to_lowercase().as_str() {
+            "h" => self.num_two > self.fib_one,
+            "l" => self.num_two < self.fib_one,
+            "s" => self.num_two == self.fib_one,
+            _ => return Err(GuppiesError::InvalidGuess(guess.to_string()))
+        };
+        Ok(correct)
+    }
+
+    fn name(&self) -> &'static str {
+        "FibonacciGuppies"
+    }
+
+    fn describe(&self) -> String {
+        "FibonacciGuppies: a number from 1 to 100 is drawn and moved to the nearest Fibonacci number (1, 2, 3, 5, 8, ...), which is shown to you.  Guess whether a second number from 1 to 100 is (h)igher, (l)ower, or the (s)ame as that Fibonacci number.  A right guess wins your bet.".to_string()
+    }
+
+    // The second number is anywhere from 1 to 100, so the Fibonacci number decides the odds
+    fn hint(&self) -> String {
+        let higher = (100 - self.fib_one) as f64 / 100.0;
+        let lower = (self.fib_one - 1) as f64 / 100.0;
+        format!("Hint: higher is {}, lower is {}, the same is {}.", likelihood(higher), likelihood(lower), likelihood(0.01))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nearest_fib_snaps_to_the_closest_fibonacci_number() {
+        // Halfway between 3 and 5, so the smaller one
+        assert_eq!(nearest_fib(4), 3);
+        assert_eq!(nearest_fib(8), 8);
+        assert_eq!(nearest_fib(20), 21);
+        assert_eq!(nearest_fib(0), 1);
+    }
+
+    #[test]
+    fn the_second_number_is_compared_to_the_fibonacci_number() {
+        let variant = FibonacciGuppies {fib_one: 21, num_two: 20};
+        assert!(variant.check_guess("l").unwrap());
+        assert!(!variant.check_guess("h").unwrap());
+        assert!(FibonacciGuppies {fib_one: 8, num_two: 8}.check_guess("s").unwrap());
+    }
+}
@@ -0,0 +1,138 @@
+// fusion.rs
+//
+// The FusionGuppies variant, where every round is two variants played at once.
+//
+
+use rand::RngCore;  // for generating random numbers
+use crate::console::Console;
+use crate::error::GuppiesError;
+use crate::strategy::Strategy;
+use super::{DifferentValues, GuppiesVariant, RoundContext, RoundOutcome};
+
+
+// The guesses for the two parts are joined with this, e.g. "h+c", so the round log and
+// check_guess get both of them as one guess
+const GUESS_SEPARATOR: char = '+';
+
+
+// FusionGuppies plays two variants in one round, and the round is only won if both guesses
+// are right.  Each part's first value is shown and guessed in turn, and then both second
+// values are drawn and shown
+pub struct FusionGuppies {
+    first: Box<dyn GuppiesVariant>,
+    second: Box<dyn GuppiesVariant>,
+}
+
+impl FusionGuppies {
+    /// Makes a FusionGuppies game out of two variants, which are played in that order every round.
+    ///
+    /// # Arguments
+    ///
+    /// * `first` - The variant played as the first part of every round.
+    /// * `second` - The variant played as the second part of every round.
+    ///
+    pub fn new(first: Box<dyn GuppiesVariant>, second: Box<dyn GuppiesVariant>) -> FusionGuppies {
+        FusionGuppies {first, second}
+    }
+}
+
+impl GuppiesVariant for FusionGuppies {
+    fn generate_first(&mut self, rng: &mut dyn RngCore) {
+        self.first.generate_first(rng);
+        self.second.generate_first(rng);
+    }
+
+    fn generate_second(&mut self, rng: &mut dyn RngCore) {
+        self.first.generate_second(rng);
+        self.second.generate_second(rng);
+    }
+
+    fn display_value(&self, which: DifferentValues) -> String {
+        format!("{} / {}", self.first.display_value(which), self.second.display_value(which))
+    }
+
+    fn reset(&mut self) {
+        self.first.reset();
+        self.second.reset();
+    }
+
+    // Asks for each part's guess in turn, letting each part give its own hint
+    fn get_guess(&self, console: &mut Console, round: &RoundContext) -> String {
+        let first = self.first.read_guess(console, round);
+        if first == "q" {
+            return first;
+        }
+        let second = self.second.read_guess(console, round);
+        if second == "q" {
+            return second;
+        }
+        format!("{}{}{}", first, GUESS_SEPARATOR, second)
+    }
+
+    // Each part's guess is the strategy's pick from that part's own hint
+    fn strategy_guess(&self, strategy: &mut dyn Strategy) -> String {
+        format!("{}{}{}", self.first.strategy_guess(strategy), GUESS_SEPARATOR, self.second.strategy_guess(strategy))
+    }
+
+    // Each part validates its own guess, and a fusion guess starts with the first part's
+    fn min_guess_options(&self) -> Vec<char> {
+        self.first.min_guess_options()
+    }
+
+    fn guess_words(&self) -> Vec<(&'static str, char)> {
+        self.first.guess_words()
+    }
+
+    // Both parts' guesses have to be right
+    fn check_guess(&self, guess:&str) -> Result<bool, GuppiesError> {
+        let Some((first, second)) = guess.split_once(GUESS_SEPARATOR) else {
+            return Err(GuppiesError::InvalidGuess(guess.to_string()));
+        };
+        Ok(self.first.check_guess(first)? && self.second.check_guess(second)?)
+    }
+
+    fn name(&self) -> &'static str {
+        "FusionGuppies"
+    }
+
+    fn describe(&self) -> String {
+        format!("FusionGuppies: every round is two games at once, {} and then {}, and it is only won if both guesses are right.  A right round wins what both games pay added together.  {}  {}",
+            self.first.name(), self.second.name(), self.first.describe(), self.second.describe())
+    }
+
+    fn hint(&self) -> String {
+        format!("{}  {}", self.first.hint(), self.second.hint())
+    }
+
+    // Winning both parts is harder than either, so it pays both payouts
+    fn payout_multiplier(&self) -> f64 {
+        self.first.payout_multiplier() + self.second.payout_multiplier()
+    }
+
+    // Plays each part's first value and guess in turn, then draws and shows both second values
+    // and says which parts were right
+    fn play_round(&mut self, console: &mut Console, rng: &mut dyn RngCore, round: &RoundContext) -> RoundOutcome {
+        let mut guesses = Vec::new();
+        for (part, variant) in [&mut self.first, &mut self.second].into_iter().enumerate() {
+            console.print(&format!("Part {} of 2: {}", part + 1, variant.name()));
+            variant.generate_first(rng);
+            variant.tell_random(console, DifferentValues::FirstGeneratedVal);
+            let guess = variant.read_guess(console, round);
+            if guess == "q" {
+                return RoundOutcome::Quit;
+            }
+            guesses.push(guess);
+        }
+
+        let mut all_correct = true;
+        for (part, (variant, guess)) in [&mut self.first, &mut self.second].into_iter().zip(&guesses).enumerate() {
+            variant.generate_second(rng);
+            variant.tell_random(console, DifferentValues::SecondGeneratedVal);
+            // Each part's get_guess only lets through guesses it can check
+            let correct = variant.check_guess(guess).unwrap_or(false);
+            console.print(&format!("Part {} was {}.", part + 1, if correct { "right" } else { "wrong" }));
+            all_correct &= correct;
+        }
+        RoundOutcome::Finished {correct: all_correct, guess: guesses.join(&GUESS_SEPARATOR.to_string())}
+    }
+}
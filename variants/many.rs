@@ -0,0 +1,237 @@
+// many.rs
+//
+// The Manyguppies variant, which plays a randomly chosen variant each round.
+//
+
+use rand::RngCore;  // for generating random numbers
+use rand::distributions::{Distribution, WeightedIndex};  // for picking variants by weight
+use crate::console::Console;
+use crate::error::GuppiesError;
+use crate::strategy::Strategy;
+use super::{DifferentValues, GuppiesVariant, RoundContext, RoundOutcome};
+
+
+/// Resource: https://stackoverflow.com/questions/36413364/as-i-can-make-the-vector-is-mutable-inside-struct
+/// Manyguppies stores all the variants in a vector and randomly chooses one
+pub struct Manyguppies{
+    // This constructor is a vector which stores GuppiesVariants
+    guppies_variants : Vec<Box<dyn GuppiesVariant>>,
+    // Index into guppies_variants of the variant being played this round.  Boxes can't be
+    // moved out of the vector, so the active variant is remembered by position instead
+    current_index : usize,
+    // How often each variant comes up, relative to the others, in the same order as guppies_variants
+    weights : Vec<u32>,
+    // Index of the variant played last round, so a switch to another one can be announced
+    last_index : Option<usize>,
+ }
+
+impl Manyguppies {
+    /// Makes a Manyguppies game that picks from the given variants each round, all of them
+    /// equally often.
+    ///
+    /// # Arguments
+    ///
+    /// * `guppies_variants` - The variants to pick from, there has to be at least one.
+    ///
+    pub fn new(guppies_variants: Vec<Box<dyn GuppiesVariant>>) -> Manyguppies {
+        assert!(!guppies_variants.is_empty(), "Manyguppies needs at least one variant to pick from");
+        let weights = vec![1; guppies_variants.len()];
+        Manyguppies {guppies_variants, current_index: 0, weights, last_index: None}
+    }
+
+    /// Makes a Manyguppies game where some variants come up more often than others.
+    /// A variant with weight 2 comes up twice as often as one with weight 1, and one with
+    /// weight 0 never comes up.
+    ///
+    /// # Arguments
+    ///
+    /// * `guppies_variants` - The variants to pick from.
+    /// * `weights` - How often each variant comes up, one weight per variant in the same order.
+    ///
+    /// # Returns
+    ///
+    /// * Result: the new Manyguppies, or GuppiesError::WeightCountMismatch if there isn't
+    ///   exactly one weight per variant, or GuppiesError::AllWeightsZero if every weight is 0.
+    ///
+    pub fn with_weights(guppies_variants: Vec<Box<dyn GuppiesVariant>>, weights: Vec<u32>) -> Result<Manyguppies, GuppiesError> {
+        if weights.len() != guppies_variants.len() {
+            return Err(GuppiesError::WeightCountMismatch { weights: weights.len(), variants: guppies_variants.len() });
+        }
+        if weights.iter().all(|&weight| weight == 0) {
+            return Err(GuppiesError::AllWeightsZero);
+        }
+        Ok(Manyguppies {guppies_variants, current_index: 0, weights, last_index: None})
+    }
+
+    // Randomly picks which variant is played this round, by weight
+    fn choose_variant(&mut self, rng: &mut dyn RngCore) {
+        let distribution = WeightedIndex::new(&self.weights).expect("weights are checked when Manyguppies is made");
+        // sets the current variant to that random index
+        self.current_index = distribution.sample(rng);
+    }
+}
+
+impl GuppiesVariant for Manyguppies  {
+    fn generate_first(&mut self, rng: &mut dyn RngCore) {
+        self.choose_variant(rng);
+        self.guppies_variants[self.current_index].generate_first(rng);
+    }
+
+    // The second value must come from the same variant that produced the first one
+    fn generate_second(&mut self, rng: &mut dyn RngCore) {
+        self.guppies_variants[self.current_index].generate_second(rng);
+    }
+    
+    // Calls the current variiants implementation of the method
+    fn tell_random(&self, console: &mut Console, value: DifferentValues) {
+        self.guppies_variants[self.current_index].tell_random(console, value)
+    }
+    fn display_value(&self, which: DifferentValues) -> String {
+        self.guppies_variants[self.current_index].display_value(which)
+    }
+    // Every game is reset, and the first one is current again like in a new Manyguppies
+    fn reset(&mut self) {
+        for variant in &mut self.guppies_variants {
+            variant.reset();
+        }
+        self.current_index = 0;
+        self.last_index = None;
+    }
+    fn get_guess(&self, console: &mut Console, round: &RoundContext) -> String {
+        self.guppies_variants[self.current_index].get_guess(console, round)
+        
+    }
+
+    fn min_guess_options(&self) -> Vec<char> {
+        self.guppies_variants[self.current_index].min_guess_options()
+    }
+    fn guess_words(&self) -> Vec<(&'static str, char)> {
+        self.guppies_variants[self.current_index].guess_words()
+    }
+    fn check_guess(&self, guess:&str) -> Result<bool, GuppiesError> {
+        self.guppies_variants[self.current_index].check_guess(guess)
+    }
+    fn name(&self) -> &'static str {
+        "Manyguppies"
+    }
+    fn active_name(&self) -> &'static str {
+        self.guppies_variants[self.current_index].active_name()
+    }
+    // Each game's rules are listed, since any of them can come up
+    fn describe(&self) -> String {
+        let rules: Vec<String> = self.guppies_variants.iter().map(|variant| variant.describe()).collect();
+        format!("Manyguppies: every round is one of these games, picked at random.  {}", rules.join("  "))
+    }
+    fn hint(&self) -> String {
+        self.guppies_variants[self.current_index].hint()
+    }
+    fn guess_chances(&self, first_value: i32) -> Vec<(char, f64)> {
+        self.guppies_variants[self.current_index].guess_chances(first_value)
+    }
+    fn strategy_guess(&self, strategy: &mut dyn Strategy) -> String {
+        self.guppies_variants[self.current_index].strategy_guess(strategy)
+    }
+    fn allows_same(&self) -> bool {
+        self.guppies_variants[self.current_index].allows_same()
+    }
+    // Pays whatever the variant that was played this round pays
+    fn payout_multiplier(&self) -> f64 {
+        self.guppies_variants[self.current_index].payout_multiplier()
+    }
+    fn same_multiplier(&self) -> f64 {
+        self.guppies_variants[self.current_index].same_multiplier()
+    }
+    fn guess_multiplier(&self, guess: &str) -> f64 {
+        self.guppies_variants[self.current_index].guess_multiplier(guess)
+    }
+    // The bet is placed before the round's variant is picked, so it has to cover any of them
+    fn max_multiplier(&self) -> f64 {
+        self.guppies_variants.iter().map(|variant| variant.max_multiplier()).fold(0.0, f64::max)
+    }
+    // Lets the chosen variant play the round its own way, in case it overrides play_round.
+    // The first round, and every round that switches to another variant, says which one it is
+    fn play_round(&mut self, console: &mut Console, rng: &mut dyn RngCore, round: &RoundContext) -> RoundOutcome {
+        self.choose_variant(rng);
+        if self.last_index != Some(self.current_index) {
+            console.print(&format!("This round is {}.", self.active_name()));
+            self.last_index = Some(self.current_index);
+        }
+        self.guppies_variants[self.current_index].play_round(console, rng, round)
+    }
+ }
+
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+    use crate::variants::{OddOrEvenGuppies, PlainGuppies, RainbowGuppies};
+    use super::*;
+
+    #[test]
+    fn switches_between_its_variants() {
+        let mut variant = Manyguppies::new(vec![Box::new(PlainGuppies::new()), Box::new(RainbowGuppies::new())]);
+        let mut rng = StdRng::seed_from_u64(3);
+        let mut played = Vec::new();
+        for _ in 0..20 {
+            variant.generate_first(&mut rng);
+            variant.generate_second(&mut rng);
+            played.push(variant.active_name());
+        }
+        assert!(played.contains(&"PlainGuppies"));
+        assert!(played.contains(&"RainbowGuppies"));
+    }
+
+    #[test]
+    fn a_variant_weighted_0_never_comes_up() {
+        let variants: Vec<Box<dyn GuppiesVariant>> = vec![Box::new(PlainGuppies::new()), Box::new(RainbowGuppies::new()), Box::new(OddOrEvenGuppies::new())];
+        let mut variant = Manyguppies::with_weights(variants, vec![0, 0, 1]).unwrap();
+        let mut rng = StdRng::seed_from_u64(40);
+        for _ in 0..50 {
+            variant.generate_first(&mut rng);
+            assert_eq!(variant.active_name(), "OddOrEvenGuppies");
+        }
+    }
+
+    #[test]
+    fn weights_have_to_match_the_variants() {
+        let variants: Vec<Box<dyn GuppiesVariant>> = vec![Box::new(PlainGuppies::new()), Box::new(RainbowGuppies::new())];
+        assert!(matches!(Manyguppies::with_weights(variants, vec![1]), Err(GuppiesError::WeightCountMismatch {weights: 1, variants: 2})));
+        let variants: Vec<Box<dyn GuppiesVariant>> = vec![Box::new(PlainGuppies::new())];
+        assert!(matches!(Manyguppies::with_weights(variants, vec![0]), Err(GuppiesError::AllWeightsZero)));
+    }
+
+    #[test]
+    fn reset_makes_the_first_variant_current_again() {
+        let mut variant = Manyguppies::with_weights(vec![Box::new(PlainGuppies::new()), Box::new(RainbowGuppies::new())], vec![0, 1]).unwrap();
+        variant.generate_first(&mut StdRng::seed_from_u64(5));
+        assert_eq!(variant.active_name(), "RainbowGuppies");
+        variant.reset();
+        assert_eq!(variant.current_index, 0);
+        assert_eq!(variant.last_index, None);
+        assert_eq!(variant.active_name(), "PlainGuppies");
+    }
+
+    #[test]
+    fn a_round_that_switches_variant_says_which_one() {
+        let mut variant = Manyguppies::new(vec![Box::new(PlainGuppies::new()), Box::new(RainbowGuppies::new())]);
+        let mut rng = StdRng::seed_from_u64(77);
+        // "s" is a guess in both PlainGuppies and RainbowGuppies, so every round is played out
+        let mut input = std::io::Cursor::new("s\n".repeat(20));
+        let mut output = Vec::new();
+        let mut console = Console { input: &mut input, output: &mut output, quiet: true };
+        let mut played = Vec::new();
+        for _ in 0..20 {
+            let outcome = variant.play_round(&mut console, &mut rng, &RoundContext::default());
+            assert!(matches!(outcome, RoundOutcome::Finished {ref guess, ..} if guess == "s"));
+            played.push(variant.active_name());
+        }
+        // The first round is announced, and after it only the rounds that changed variant
+        let mut expected = vec![played[0]];
+        expected.extend(played.windows(2).filter(|pair| pair[0] != pair[1]).map(|pair| pair[1]));
+        let output = String::from_utf8(output).unwrap();
+        let announced: Vec<&str> = output.lines().filter_map(|line| line.strip_prefix("This round is ")?.strip_suffix('.')).collect();
+        assert!(expected.len() > 1);
+        assert_eq!(announced, expected);
+    }
+}
@@ -0,0 +1,163 @@
+// modular.rs
+//
+// The ModularArithmeticGuppies variant, comparing the two numbers' remainders.
+//
+
+use rand::{Rng, RngCore};  // for generating random numbers
+use crate::console::Console;
+use crate::error::GuppiesError;
+use super::{DifferentValues, GuppiesVariant, RoundContext, likelihood};
+
+
+// The moduli go from 2 to 10 inclusive.  A modulus of 1 is left out because every
+// remainder would be 0, so the answer would always be "same".
+const MIN_MODULUS: i32 = 2;
+const MAX_MODULUS: i32 = 10;
+
+
+// ModularArithmeticGuppies draws two numbers from 1 to 100 and a modulus, and asks whether the
+// second number's remainder is higher, lower, or the same as the first number's
+pub struct ModularArithmeticGuppies {
+    num_one: i32,
+    num_two: i32,
+    modulus: i32,
+}
+
+impl ModularArithmeticGuppies {
+    /// Makes a ModularArithmeticGuppies game, with values from 1 to 100 inclusive and a
+    /// modulus from 2 to 10 inclusive.
+    pub fn new() -> ModularArithmeticGuppies {
+        ModularArithmeticGuppies {num_one: 0, num_two: 0, modulus: MIN_MODULUS}
+    }
+}
+
+impl Default for ModularArithmeticGuppies {
+    fn default() -> ModularArithmeticGuppies {
+        ModularArithmeticGuppies::new()
+    }
+}
+
+// This struct implements GuppiesVariant
+impl GuppiesVariant for ModularArithmeticGuppies {
+    fn generate_first(&mut self, rng: &mut dyn RngCore) {
+        // The modulus is part of what the user sees before guessing, so it goes with the first number
+        self.num_one = rng.gen_range(1..101);
+        self.modulus = rng.gen_range(MIN_MODULUS..MAX_MODULUS + 1);
+    }
+
+    fn generate_second(&mut self, rng: &mut dyn RngCore) {
+        // Only the second number is rolled, so the first one and the modulus stay what the user saw
+        self.num_two = rng.gen_range(1..101);
+    }
+
+    fn tell_random(&self, console: &mut Console, value: DifferentValues) {
+        match value {
+            DifferentValues::FirstGeneratedVal => {
+                console.print(&format!("This is the first number {} and the modulus {}, so the first remainder is {}",
+                    self.num_one, self.modulus, self.num_one % self.modulus))
+            }
+            DifferentValues::SecondGeneratedVal => {
+                console.print(&format!("This is the second number {}, so the second remainder is {}",
+                    self.num_two, self.num_two % self.modulus))
+            }
+        }
+    }
+
+    // The modulus goes with the first number, since that's when the user sees it
+    fn display_value(&self, which: DifferentValues) -> String {
+        match which {
+            DifferentValues::FirstGeneratedVal => format!("{} mod {}", self.num_one, self.modulus),
+            DifferentValues::SecondGeneratedVal => self.num_two.to_string(),
+        }
+    }
+
+    fn reset(&mut self) {
+        *self = ModularArithmeticGuppies::new();
+    }
+
+    // Gets the guess from the user and makes sure its not invalid
+    fn get_guess(&self, console: &mut Console, round: &RoundContext) -> String {
+        self.prompt_guess(console, round, "Is the second remainder (h)igher, (l)ower, or the (s)ame?   [Or (q)uit, or ? for a hint.]", &self.min_guess_options())
+    }
+
+    fn min_guess_options(&self) -> Vec<char> {
+        vec!['h', 'l', 's']
+    }
+
+    // Checks the users guesses against the remainders, not the numbers themselves
+    fn check_guess(&self, guess:&str) -> Result<bool, GuppiesError> {
+        let first = self.num_one % self.modulus;
+        let second = self.num_two % self.modulus;
+        let correct = match guess.to_lowercase().as_str() {
+            "h" => second > first,
+            "l" => second < first,
+            "s" => second == first,
+            _ => return Err(GuppiesError::InvalidGuess(guess.to_string()))
+        };
+        Ok(correct)
+    }
+
+    fn name(&self) -> &'static str {
+        "ModularArithmeticGuppies"
+    }
+
+    fn describe(&self) -> String {
+        "ModularArithmeticGuppies: two numbers are drawn from 1 to 100, along with a modulus from 2 to 10.  After seeing the first number and the modulus, guess whether the second number's remainder is (h)igher, (l)ower, or the (s)ame as the first's.  A right guess wins your bet.".to_string()
+    }
+
+    // Remainders are close to evenly spread from 0 to modulus - 1, so the first remainder
+    // decides how much room there is above and below it
+    fn hint(&self) -> String {
+        let first = self.num_one % self.modulus;
+        let modulus = self.modulus as f64;
+        let higher = (self.modulus - 1 - first) as f64 / modulus;
+        let lower = first as f64 / modulus;
+        format!("Hint: higher is {}, lower is {}, the same is {}.", likelihood(higher), likelihood(lower), likelihood(1.0 / modulus))
+    }
+
+    // With a small modulus the same remainder comes up a lot (half the time mod 2), so it
+    // pays like any other guess
+    fn same_multiplier(&self) -> f64 {
+        self.payout_multiplier()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+    use super::*;
+
+    // The guess that is right for each (first number, second number, modulus)
+    const KNOWN: [(i32, i32, i32, &str); 4] = [
+        // 17 mod 5 is 2 and 23 mod 5 is 3
+        (17, 23, 5, "h"),
+        // 10 mod 3 is 1 and 30 mod 3 is 0, though 30 is the bigger number
+        (10, 30, 3, "l"),
+        // 9 mod 4 and 13 mod 4 are both 1
+        (9, 13, 4, "s"),
+        // 99 mod 10 is 9 and 1 mod 10 is 1
+        (99, 1, 10, "l"),
+    ];
+
+    #[test]
+    fn the_remainders_are_compared() {
+        for (num_one, num_two, modulus, right) in KNOWN {
+            let variant = ModularArithmeticGuppies {num_one, num_two, modulus};
+            for guess in ["h", "l", "s"] {
+                assert_eq!(variant.check_guess(guess).unwrap(), guess == right, "{} and {} mod {}", num_one, num_two, modulus);
+            }
+        }
+    }
+
+    #[test]
+    fn the_modulus_is_never_1() {
+        let mut variant = ModularArithmeticGuppies::new();
+        let mut rng = StdRng::seed_from_u64(32);
+        for _ in 0..200 {
+            variant.generate_first(&mut rng);
+            assert!((MIN_MODULUS..=MAX_MODULUS).contains(&variant.modulus));
+        }
+    }
+}
@@ -0,0 +1,235 @@
+// odd_or_even.rs
+//
+// The OddOrEvenGuppies variant, guessing the parity of the second number.
+//
+
+use rand::{Rng, RngCore};  // for generating random numbers
+use crate::console::Console;
+use crate::error::GuppiesError;
+use super::{DifferentValues, GuppiesVariant, RoundContext, likelihood};
+
+
+// Struct OddOrEvenGuppies is a game which asks the user if the number is odd or even
+// Two constructors, num_one and num_two which are of type i32
+// min and max are the inclusive range both numbers are drawn from
+// allow_same is whether "(s)ame", the same parity as the first number, is one of the guesses
+pub struct OddOrEvenGuppies {
+    num_one: i32,
+    num_two: i32,
+    min: i32,
+    max: i32,
+    allow_same: bool,
+}
+
+impl OddOrEvenGuppies {
+    /// Makes an OddOrEvenGuppies game, with values from 1 to 100 inclusive.
+    pub fn new() -> OddOrEvenGuppies {
+        OddOrEvenGuppies::build(1, 100, true)
+    }
+
+    // Every constructor (and reset) starts both numbers at the bottom of the range
+    fn build(min: i32, max: i32, allow_same: bool) -> OddOrEvenGuppies {
+        OddOrEvenGuppies {num_one: min, num_two: min, min, max, allow_same}
+    }
+
+    /// Makes an OddOrEvenGuppies game without the "(s)ame" guess, so the choice is just odd
+    /// or even.
+    pub fn without_same() -> OddOrEvenGuppies {
+        OddOrEvenGuppies::build(1, 100, false)
+    }
+
+    /// Makes an OddOrEvenGuppies game that draws its numbers from a custom range.
+    ///
+    /// # Arguments
+    ///
+    /// * `min` - The smallest number that can be drawn.
+    /// * `max` - The largest number that can be drawn, must be bigger than min.
+    ///
+    /// # Returns
+    ///
+    /// * Result: the new OddOrEvenGuppies, or GuppiesError::OutOfRange if min isn't less than max.
+    ///
+    pub fn with_range(min: i32, max: i32) -> Result<OddOrEvenGuppies, GuppiesError> {
+        if min >= max {
+            return Err(GuppiesError::OutOfRange {min, max});
+        }
+        Ok(OddOrEvenGuppies::build(min, max, true))
+    }
+
+    // The fraction of the range that is odd, which is only exactly half for ranges that
+    // start and end on different parities.  The numbers alternate, so a range starting on an
+    // odd number has the extra one when its size is odd.  Counted in i64, since a range as
+    // wide as an i32 has more numbers than an i32 can count
+    fn odd_chance(&self) -> f64 {
+        let total = self.max as i64 - self.min as i64 + 1;
+        let odd = if self.min.rem_euclid(2) == 1 { (total + 1) / 2 } else { total / 2 };
+        odd as f64 / total as f64
+    }
+}
+
+// This struct implements GuppiesVariant
+impl Default for OddOrEvenGuppies {
+    fn default() -> OddOrEvenGuppies {
+        OddOrEvenGuppies::new()
+    }
+}
+
+impl GuppiesVariant for OddOrEvenGuppies {
+    fn generate_first(&mut self, rng: &mut dyn RngCore) {
+        // Genereates the first random number and assigns it to the struct
+        self.num_one = rng.gen_range(self.min..=self.max);
+    }
+
+    fn generate_second(&mut self, rng: &mut dyn RngCore) {
+        // Only the second number is rolled, so the first one stays what the user saw
+        self.num_two = rng.gen_range(self.min..=self.max);
+    }
+
+    fn tell_random(&self, console: &mut Console, value: DifferentValues) {
+        console.print(&format!("This is the {} number {}", value.ordinal(), self.display_value(value)))
+    }
+
+    fn display_value(&self, which: DifferentValues) -> String {
+        match which {
+            DifferentValues::FirstGeneratedVal => self.num_one.to_string(),
+            DifferentValues::SecondGeneratedVal => self.num_two.to_string(),
+        }
+    }
+
+    // The range and whether "same" is allowed are settings, not part of a game, so they are kept
+    fn reset(&mut self) {
+        *self = OddOrEvenGuppies::build(self.min, self.max, self.allow_same);
+    }
+
+    // Gets the guess from the user and makes sure its not invalid, returns a guess as
+    fn get_guess(&self, console: &mut Console, round: &RoundContext) -> String {
+        let question = if self.allows_same() {
+            "Is the second num (o)dd, (e)ven, or the (s)ame parity as the first? [Or (q)uit, or ? for a hint.]"
+        }
+        else {
+            "Is the second num (o)dd or (e)ven? [Or (q)uit, or ? for a hint.]"
+        };
+        self.prompt_guess(console, round, question, &self.min_guess_options())
+    }
+
+    fn min_guess_options(&self) -> Vec<char> {
+        if self.allows_same() { vec!['o', 'e', 's'] } else { vec!['o', 'e'] }
+    }
+
+    // "same" is only taken when it is one of the guesses, like 's' itself
+    fn guess_words(&self) -> Vec<(&'static str, char)> {
+        vec![("odd", 'o'), ("even", 'e'), ("same", 's')]
+    }
+
+    // Checks the users guesses
+    fn check_guess(&self, guess:&str) -> Result<bool, GuppiesError> {
+       let correct = match guess.to_lowercase().as_str() {
+        // odd if there is a remainder
+        "o" => self.num_two % 2 != 0, 
+        // even if there is no remainder
+        "e" => self.num_two % 2 == 0,
+        // the same parity if both remainders match.  rem_euclid keeps the remainder of a
+        // negative number at 0 or 1, like a positive one's
+        "s" if self.allows_same() => self.num_two.rem_euclid(2) == self.num_one.rem_euclid(2),
+        _ => return Err(GuppiesError::InvalidGuess(guess.to_string()))
+       };
+       Ok(correct)
+    }
+
+    fn name(&self) -> &'static str {
+        "OddOrEvenGuppies"
+    }
+
+    fn describe(&self) -> String {
+        let guesses = if self.allows_same() { "(o)dd, (e)ven, or the (s)ame parity (odd or even) as the first" } else { "(o)dd or (e)ven" };
+        format!("OddOrEvenGuppies: two numbers are drawn from {} to {}.  After seeing the first one, guess whether the second is {}.  A right guess wins one and a half times your bet.", self.min, self.max, guesses)
+    }
+
+    // Counts the odd numbers in the range, and the same parity is whichever the first number has
+    fn hint(&self) -> String {
+        let odd = self.odd_chance();
+        if self.allows_same() {
+            let same = if self.num_one % 2 != 0 { odd } else { 1.0 - odd };
+            format!("Hint: odd is {}, even is {}, the same is {}.", likelihood(odd), likelihood(1.0 - odd), likelihood(same))
+        }
+        else {
+            format!("Hint: odd is {}, even is {}.", likelihood(odd), likelihood(1.0 - odd))
+        }
+    }
+
+    // The same parity is whichever the first number has
+    fn guess_chances(&self, first_value: i32) -> Vec<(char, f64)> {
+        let odd = self.odd_chance();
+        let mut chances = vec![('o', odd), ('e', 1.0 - odd)];
+        if self.allows_same() {
+            chances.push(('s', if first_value % 2 != 0 { odd } else { 1.0 - odd }));
+        }
+        chances
+    }
+
+    fn allows_same(&self) -> bool {
+        self.allow_same
+    }
+
+    // Parity is a coin flip, but it pays more than plain to keep the variant worth picking
+    fn payout_multiplier(&self) -> f64 {
+        1.5
+    }
+
+    // The same parity is as likely as odd or even, so it pays no more than they do
+    fn same_multiplier(&self) -> f64 {
+        self.payout_multiplier()
+    }
+ } 
+
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+    use super::*;
+
+    #[test]
+    fn without_same_rejects_s() {
+        let variant = OddOrEvenGuppies::without_same();
+        assert!(!variant.allows_same());
+        assert!(matches!(variant.check_guess("s"), Err(GuppiesError::InvalidGuess(..))));
+
+        let mut input = Cursor::new("s\ne\n");
+        let mut output = Vec::new();
+        let console = &mut Console { input: &mut input, output: &mut output, quiet: false };
+        assert_eq!(variant.get_guess(console, &RoundContext::default()), "e");
+        let printed = String::from_utf8(output).unwrap();
+        assert!(printed.contains("Invalid guess."));
+        assert!(!printed.contains("(s)ame"));
+    }
+
+    #[test]
+    fn same_means_the_same_parity() {
+        let variant = |num_one, num_two| OddOrEvenGuppies {num_one, num_two, ..OddOrEvenGuppies::new()};
+        assert!(variant(4, 18).check_guess("s").unwrap());
+        assert!(variant(7, 99).check_guess("s").unwrap());
+        assert!(!variant(4, 17).check_guess("s").unwrap());
+        assert!(variant(-3, 5).check_guess("s").unwrap());
+    }
+
+    #[test]
+    fn a_custom_range_draws_from_it_and_checks_parity() {
+        let mut variant = OddOrEvenGuppies::with_range(1, 11).unwrap();
+        let mut rng = StdRng::seed_from_u64(92);
+        for _ in 0..100 {
+            variant.generate_first(&mut rng);
+            variant.generate_second(&mut rng);
+            assert!((1..=11).contains(&variant.num_one) && (1..=11).contains(&variant.num_two));
+            assert_eq!(variant.check_guess("o").unwrap(), variant.num_two % 2 == 1);
+            assert_eq!(variant.check_guess("e").unwrap(), variant.num_two % 2 == 0);
+        }
+    }
+
+    #[test]
+    fn a_range_needs_min_below_max() {
+        assert!(matches!(OddOrEvenGuppies::with_range(11, 1), Err(GuppiesError::OutOfRange {min: 11, max: 1})));
+        assert!(matches!(OddOrEvenGuppies::with_range(5, 5), Err(GuppiesError::OutOfRange {..})));
+    }
+}
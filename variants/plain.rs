@@ -0,0 +1,249 @@
+// plain.rs
+//
+// The PlainGuppies variant, the original higher/lower/same game.
+//
+
+use rand::{Rng, RngCore};  // for generating random numbers
+use crate::console::Console;
+use crate::error::GuppiesError;
+use super::{DifferentValues, GuppiesVariant, RoundContext, likelihood, odds};
+
+
+/// Works out the better guess for the second value once the first is known: higher if the
+/// first value is in the bottom half of the range, lower if it is in the top half.  Right in
+/// the middle both are as good, and higher is suggested.
+///
+/// # Arguments
+///
+/// * `first` - The first value.
+/// * `min` - The smallest value that can be drawn.
+/// * `max` - The largest value that can be drawn.
+///
+/// # Returns
+///
+/// * &str: "h" for higher or "l" for lower.
+///
+pub fn optimal_guess(first: i32, min: i32, max: i32) -> &'static str {
+    // Worked out in i64, since a range can span more than an i32 can count
+    if max as i64 - first as i64 >= first as i64 - min as i64 {
+        "h"
+    }
+    else {
+        "l"
+    }
+}
+
+
+// Plain guppies is the original variant
+// min and max are the inclusive range both values are drawn from
+// assist prints the better guess after the first value, as a learning aid
+pub struct PlainGuppies {
+    value_one: i32,
+    value_two: i32,
+    min: i32,
+    max: i32,
+    assist: bool,
+}
+
+impl PlainGuppies {
+    /// Makes the standard PlainGuppies game, with values from 1 to 10 inclusive.
+    pub fn new() -> PlainGuppies {
+        PlainGuppies {value_one: 1, value_two: 1, min: 1, max: 10, assist: false}
+    }
+
+    /// Makes a PlainGuppies game that draws its values from a custom range.
+    ///
+    /// # Arguments
+    ///
+    /// * `min` - The smallest value that can be drawn.
+    /// * `max` - The largest value that can be drawn, must be bigger than min.
+    ///
+    /// # Returns
+    ///
+    /// * Result: the new PlainGuppies, or GuppiesError::OutOfRange if min isn't less than max.
+    ///
+    pub fn with_range(min: i32, max: i32) -> Result<PlainGuppies, GuppiesError> {
+        if min >= max {
+            return Err(GuppiesError::OutOfRange {min, max});
+        }
+        Ok(PlainGuppies {value_one: min, value_two: min, min, max, assist: false})
+    }
+
+    /// Turns on assist mode, which suggests the better guess after the first value is shown.
+    pub fn with_assist(mut self) -> PlainGuppies {
+        self.assist = true;
+        self
+    }
+}
+
+impl Default for PlainGuppies {
+    fn default() -> PlainGuppies {
+        PlainGuppies::new()
+    }
+}
+
+impl GuppiesVariant for PlainGuppies {
+    fn generate_first(&mut self, rng: &mut dyn RngCore) {
+        // generates the first random value in the range and sets it on the struct
+        self.value_one = rng.gen_range(self.min..=self.max);
+    }
+
+    fn generate_second(&mut self, rng: &mut dyn RngCore) {
+        // generates only the second value, the first one is kept for check_guess
+        self.value_two = rng.gen_range(self.min..=self.max);
+    }
+    // outputs the two values to the user
+    fn tell_random(&self, console: &mut Console, value: DifferentValues) {
+        console.print(&format!("This is the {} value {}", value.ordinal(), self.display_value(value)));
+        // The suggestion only helps before the guess, so it comes with the first value
+        if self.assist && value == DifferentValues::FirstGeneratedVal {
+            let suggestion = if optimal_guess(self.value_one, self.min, self.max) == "h" { "(h)igher" } else { "(l)ower" };
+            console.print(&format!("Assist: the better guess is {}.", suggestion));
+        }
+    }
+
+    fn display_value(&self, which: DifferentValues) -> String {
+        match which {
+            DifferentValues::FirstGeneratedVal => self.value_one.to_string(),
+            DifferentValues::SecondGeneratedVal => self.value_two.to_string(),
+        }
+    }
+
+    // The range and assist are settings, not part of a game, so they are kept
+    fn reset(&mut self) {
+        self.value_one = self.min;
+        self.value_two = self.min;
+    }
+
+    //  Gets the guess from the user
+    fn get_guess(&self, console: &mut Console, round: &RoundContext) -> String {
+        self.prompt_guess(console, round, "Is the second number (h)igher, (l)ower, or the (s)ame?   [Or (q)uit, or ? for a hint.]", &self.min_guess_options())
+    }
+
+    fn min_guess_options(&self) -> Vec<char> {
+        vec!['h', 'l', 's']
+    }
+
+    // checks the validity of the guess
+    fn check_guess(&self, guess:&str) -> Result<bool, GuppiesError> {
+       let correct = match guess.to_lowercase().as_str() {
+        "h" => self.value_two > self.value_one,
+        "l" => self.value_two < self.value_one,
+        "s" => self.value_two == self.value_one,
+        _ => return Err(GuppiesError::InvalidGuess(guess.to_string()))
+       };
+       Ok(correct)
+    }
+
+    fn name(&self) -> &'static str {
+        "PlainGuppies"
+    }
+
+    fn describe(&self) -> String {
+        format!("PlainGuppies: two numbers are drawn from {} to {}.  After seeing the first one, guess whether the second is (h)igher, (l)ower, or the (s)ame.  A right guess wins your bet.", self.min, self.max)
+    }
+
+    // Counts how many values in the range are above and below the first value
+    fn hint(&self) -> String {
+        let (higher, lower, same) = odds(self.value_one, self.min, self.max);
+        format!("Hint: higher is {}, lower is {}, the same is {}.",
+            likelihood(higher), likelihood(lower), likelihood(same))
+    }
+
+    fn guess_chances(&self, first_value: i32) -> Vec<(char, f64)> {
+        let (higher, lower, same) = odds(first_value, self.min, self.max);
+        vec![('h', higher), ('l', lower), ('s', same)]
+    }
+ }
+
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+    use super::*;
+
+    #[test]
+    fn a_dice_range_only_draws_one_to_six() {
+        let mut variant = PlainGuppies::with_range(1, 6).unwrap();
+        let mut rng = StdRng::seed_from_u64(8);
+        for _ in 0..100 {
+            variant.generate_first(&mut rng);
+            variant.generate_second(&mut rng);
+            assert!((1..=6).contains(&variant.value_one));
+            assert!((1..=6).contains(&variant.value_two));
+        }
+    }
+
+    #[test]
+    fn a_range_needs_min_below_max() {
+        assert!(matches!(PlainGuppies::with_range(6, 6), Err(GuppiesError::OutOfRange {min: 6, max: 6})));
+        assert!(matches!(PlainGuppies::with_range(10, 1), Err(GuppiesError::OutOfRange {..})));
+    }
+
+    #[test]
+    fn an_unknown_guess_is_an_error() {
+        assert!(matches!(PlainGuppies::new().check_guess("x"), Err(GuppiesError::InvalidGuess(guess)) if guess == "x"));
+    }
+
+    // A PlainGuppies game over 1 to 10 showing first as its first value
+    fn showing(first: i32) -> PlainGuppies {
+        PlainGuppies {value_one: first, ..PlainGuppies::new()}
+    }
+
+    #[test]
+    fn the_hint_at_the_bottom_of_the_range() {
+        assert_eq!(showing(1).hint(), "Hint: higher is likely, lower is impossible, the same is unlikely.");
+    }
+
+    #[test]
+    fn the_hint_at_the_top_of_the_range() {
+        assert_eq!(showing(10).hint(), "Hint: higher is impossible, lower is likely, the same is unlikely.");
+    }
+
+    #[test]
+    fn a_hint_does_not_use_up_the_guess() {
+        let mut input = Cursor::new("?\nh\n");
+        let mut output = Vec::new();
+        let console = &mut Console { input: &mut input, output: &mut output, quiet: true };
+        assert_eq!(showing(1).read_guess(console, &RoundContext::default()), "h");
+        assert!(String::from_utf8(output).unwrap().contains("higher is likely"));
+    }
+
+    #[test]
+    fn reset_puts_the_values_back_and_keeps_the_range() {
+        let mut variant = PlainGuppies::with_range(5, 50).unwrap();
+        let mut rng = StdRng::seed_from_u64(2);
+        variant.generate_first(&mut rng);
+        variant.generate_second(&mut rng);
+        variant.reset();
+        assert_eq!((variant.value_one, variant.value_two), (5, 5));
+        assert_eq!((variant.min, variant.max), (5, 50));
+    }
+
+    #[test]
+    fn the_optimal_guess_points_away_from_the_near_end() {
+        assert_eq!(optimal_guess(2, 1, 10), "h");
+        assert_eq!(optimal_guess(9, 1, 10), "l");
+    }
+
+    #[test]
+    fn assist_suggests_a_guess_with_the_first_value() {
+        let variant = PlainGuppies {assist: true, ..showing(9)};
+        let mut input = std::io::empty();
+        let mut output = Vec::new();
+        let mut console = Console { input: &mut input, output: &mut output, quiet: false };
+        variant.tell_random(&mut console, DifferentValues::FirstGeneratedVal);
+        variant.tell_random(&mut console, DifferentValues::SecondGeneratedVal);
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!(output.matches("Assist: the better guess is (l)ower.").count(), 1);
+    }
+
+    #[test]
+    fn the_values_are_shown_as_integers() {
+        let variant = PlainGuppies {value_two: 10, ..showing(7)};
+        assert_eq!(variant.display_value(DifferentValues::FirstGeneratedVal), "7");
+        assert_eq!(variant.display_value(DifferentValues::SecondGeneratedVal), "10");
+    }
+}
@@ -0,0 +1,201 @@
+// prime.rs
+//
+// The PrimeGuppies variant, guessing whether the second number is prime.
+//
+
+use rand::{Rng, RngCore};  // for generating random numbers
+use crate::console::Console;
+use crate::error::GuppiesError;
+use super::{DifferentValues, GuppiesVariant, MAX_ODDS_MULTIPLIER, RoundContext, describe_payouts, likelihood, pay_by_odds};
+
+
+// The numbers go from 2 to 100 inclusive.  1 is left out because it is neither prime nor
+// composite, so every number the user sees is one or the other.
+const PRIME_MIN: i32 = 2;
+const PRIME_MAX: i32 = 100;
+
+// There are 25 primes from 2 to 100
+const PRIME_COUNT: f64 = 25.0;
+
+
+/// Checks whether a number is prime, i.e. greater than 1 and only divisible by 1 and itself.
+///
+/// # Arguments
+///
+/// * `n` - The number to check.
+///
+/// # Returns
+///
+/// * bool: True if n is prime, False otherwise (including for 1, 0, and negative numbers).
+///
+fn is_prime(n: i32) -> bool {
+    if n < 2 {
+        return false;
+    }
+    let mut divisor = 2;
+    while divisor * divisor <= n {
+        if n % divisor == 0 {
+            return false;
+        }
+        divisor += 1;
+    }
+    true
+}
+
+
+/// Works out the chance of each guess, given the first number.  About a quarter of the numbers
+/// are prime, and the first number decides higher and lower.
+///
+/// # Arguments
+///
+/// * `first` - The first number.
+///
+fn guess_chances_after(first: i32) -> Vec<(char, f64)> {
+    let total = (PRIME_MAX - PRIME_MIN + 1) as f64;
+    vec![
+        ('p', PRIME_COUNT / total),
+        ('c', 1.0 - PRIME_COUNT / total),
+        ('h', (PRIME_MAX - first) as f64 / total),
+        ('l', (first - PRIME_MIN) as f64 / total),
+        ('s', 1.0 / total),
+    ]
+}
+
+
+// Struct PrimeGuppies is a game which asks the user if the second number is prime or composite,
+// or how it compares to the first one
+pub struct PrimeGuppies {
+    num_one: i32,
+    num_two: i32,
+}
+
+impl PrimeGuppies {
+    /// Makes a PrimeGuppies game, with values from 2 to 100 inclusive.
+    pub fn new() -> PrimeGuppies {
+        PrimeGuppies {num_one: 0, num_two: 0}
+    }
+}
+
+impl Default for PrimeGuppies {
+    fn default() -> PrimeGuppies {
+        PrimeGuppies::new()
+    }
+}
+
+// This struct implements GuppiesVariant
+impl GuppiesVariant for PrimeGuppies {
+    fn generate_first(&mut self, rng: &mut dyn RngCore) {
+        self.num_one = rng.gen_range(PRIME_MIN..PRIME_MAX + 1);
+    }
+
+    fn generate_second(&mut self, rng: &mut dyn RngCore) {
+        // Only the second number is rolled, so the first one stays what the user saw
+        self.num_two = rng.gen_range(PRIME_MIN..PRIME_MAX + 1);
+    }
+
+    fn tell_random(&self, console: &mut Console, value: DifferentValues) {
+        let shown = format!("This is the {} number {}", value.ordinal(), self.display_value(value));
+        match value {
+            // Each guess is paid by its odds, which depend on the first number, so they're shown with it
+            DifferentValues::FirstGeneratedVal => console.print(&format!("{}\n{}", shown, describe_payouts(&guess_chances_after(self.num_one)))),
+            DifferentValues::SecondGeneratedVal => console.print(&shown),
+        }
+    }
+
+    fn display_value(&self, which: DifferentValues) -> String {
+        match which {
+            DifferentValues::FirstGeneratedVal => self.num_one.to_string(),
+            DifferentValues::SecondGeneratedVal => self.num_two.to_string(),
+        }
+    }
+
+    fn reset(&mut self) {
+        *self = PrimeGuppies::new();
+    }
+
+    // Gets the guess from the user and makes sure its not invalid
+    fn get_guess(&self, console: &mut Console, round: &RoundContext) -> String {
+        self.prompt_guess(console, round, "Is the second number (p)rime or (c)omposite? Or is it (h)igher, (l)ower, or the (s)ame? [Or (q)uit, or ? for a hint.]", &self.min_guess_options())
+    }
+
+    fn min_guess_options(&self) -> Vec<char> {
+        vec!['p', 'c', 'h', 'l', 's']
+    }
+
+    fn guess_words(&self) -> Vec<(&'static str, char)> {
+        vec![("prime", 'p'), ("composite", 'c'), ("higher", 'h'), ("lower", 'l'), ("same", 's')]
+    }
+
+    // Checks the users guesses
+    fn check_guess(&self, guess:&str) -> Result<bool, GuppiesError> {
+        let correct = match guess.to_lowercase().as_str() {
+            "p" => is_prime(self.num_two),
+            // Every number in the game is at least 2, so not prime means composite
+            "c" => !is_prime(self.num_two),
+            "h" => self.num_two > self.num_one,
+            "l" => self.num_two < self.num_one,
+            "s" => self.num_two == self.num_one,
+            _ => return Err(GuppiesError::InvalidGuess(guess.to_string()))
+        };
+        Ok(correct)
+    }
+
+    fn name(&self) -> &'static str {
+        "PrimeGuppies"
+    }
+
+    fn describe(&self) -> String {
+        "PrimeGuppies: two numbers are drawn from 2 to 100.  After seeing the first one, guess whether the second is (p)rime or (c)omposite, or whether it is (h)igher, (l)ower, or the (s)ame as the first.  Composite is right about three times in four, so each guess is paid by its odds, which are shown with the first number.".to_string()
+    }
+
+    fn hint(&self) -> String {
+        let chances = guess_chances_after(self.num_one);
+        format!("Hint: prime is {}, composite is {}, higher is {}, lower is {}, the same is {}.",
+            likelihood(chances[0].1), likelihood(chances[1].1), likelihood(chances[2].1), likelihood(chances[3].1), likelihood(chances[4].1))
+    }
+
+    fn guess_chances(&self, first_value: i32) -> Vec<(char, f64)> {
+        guess_chances_after(first_value)
+    }
+
+    // Composite wins 74 times in 99, so paying every guess the same would make always guessing
+    // it a winning strategy.  Each guess is paid by its odds instead
+    fn guess_multiplier(&self, guess: &str) -> f64 {
+        pay_by_odds(&guess_chances_after(self.num_one), guess)
+    }
+
+    // The same is paid by its odds like every other guess, so it has no bigger payout of its own
+    fn same_multiplier(&self) -> f64 {
+        self.payout_multiplier()
+    }
+
+    fn max_multiplier(&self) -> f64 {
+        MAX_ODDS_MULTIPLIER
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn is_prime_on_small_numbers() {
+        assert!(!is_prime(1));
+        assert!(is_prime(2));
+        assert!(is_prime(17));
+        assert!(!is_prime(20));
+    }
+
+    #[test]
+    fn prime_and_composite_guesses_are_checked_on_the_second_number() {
+        let variant = PrimeGuppies {num_one: 20, num_two: 17};
+        assert!(variant.check_guess("p").unwrap());
+        assert!(!variant.check_guess("c").unwrap());
+        assert!(variant.check_guess("l").unwrap());
+
+        let variant = PrimeGuppies {num_one: 17, num_two: 20};
+        assert!(variant.check_guess("c").unwrap());
+        assert!(variant.check_guess("h").unwrap());
+    }
+}
@@ -0,0 +1,164 @@
+// probability.rs
+//
+// The ProbabilityGuppies variant, a higher/lower/same game that shows the exact odds.
+//
+
+use rand::{Rng, RngCore};  // for generating random numbers
+use crate::console::Console;
+use crate::error::GuppiesError;
+use super::{DifferentValues, GuppiesVariant, RoundContext};
+
+
+// Both numbers are drawn from 1 to 10
+const PROBABILITY_MIN: i32 = 1;
+const PROBABILITY_MAX: i32 = 10;
+
+
+/// Works out the chance of each guess being right once the first value is known, given that
+/// the second value is equally likely to be anything from min to max.
+///
+/// # Arguments
+///
+/// * `first` - The first value.
+/// * `min` - The smallest value that can be drawn.
+/// * `max` - The largest value that can be drawn.
+///
+/// # Returns
+///
+/// * (f64, f64, f64): the chances of higher, lower, and the same, each from 0.0 to 1.0.
+///
+pub fn odds(first: i32, min: i32, max: i32) -> (f64, f64, f64) {
+    // Counted in i64, since a range as wide as an i32 has more values than an i32 can count
+    let total = (max as i64 - min as i64 + 1) as f64;
+    let higher = (max as i64 - first as i64) as f64 / total;
+    let lower = (first as i64 - min as i64) as f64 / total;
+    (higher, lower, 1.0 / total)
+}
+
+
+// ProbabilityGuppies is PlainGuppies as a lesson: once the first number is shown, it spells
+// out the exact chance of the second one being higher, lower, or the same
+pub struct ProbabilityGuppies {
+    value_one: i32,
+    value_two: i32,
+}
+
+impl ProbabilityGuppies {
+    /// Makes a ProbabilityGuppies game, with values from 1 to 10 inclusive.
+    pub fn new() -> ProbabilityGuppies {
+        ProbabilityGuppies {value_one: PROBABILITY_MIN, value_two: PROBABILITY_MIN}
+    }
+}
+
+impl Default for ProbabilityGuppies {
+    fn default() -> ProbabilityGuppies {
+        ProbabilityGuppies::new()
+    }
+}
+
+// This struct implements GuppiesVariant
+impl GuppiesVariant for ProbabilityGuppies {
+    fn generate_first(&mut self, rng: &mut dyn RngCore) {
+        self.value_one = rng.gen_range(PROBABILITY_MIN..PROBABILITY_MAX + 1);
+    }
+
+    fn generate_second(&mut self, rng: &mut dyn RngCore) {
+        // Only the second number is rolled, so the first one stays what the user saw
+        self.value_two = rng.gen_range(PROBABILITY_MIN..PROBABILITY_MAX + 1);
+    }
+
+    // The odds come with the first value, so they are there when the guess is made
+    fn tell_random(&self, console: &mut Console, value: DifferentValues) {
+        console.print(&format!("This is the {} value {}", value.ordinal(), self.display_value(value)));
+        if value == DifferentValues::FirstGeneratedVal {
+            let (higher, lower, same) = odds(self.value_one, PROBABILITY_MIN, PROBABILITY_MAX);
+            console.print(&format!("The odds: higher {:.0}%, lower {:.0}%, the same {:.0}%.", higher * 100.0, lower * 100.0, same * 100.0));
+        }
+    }
+
+    fn display_value(&self, which: DifferentValues) -> String {
+        match which {
+            DifferentValues::FirstGeneratedVal => self.value_one.to_string(),
+            DifferentValues::SecondGeneratedVal => self.value_two.to_string(),
+        }
+    }
+
+    fn reset(&mut self) {
+        *self = ProbabilityGuppies::new();
+    }
+
+    // Gets the guess from the user and makes sure its not invalid
+    fn get_guess(&self, console: &mut Console, round: &RoundContext) -> String {
+        self.prompt_guess(console, round, "Is the second number (h)igher, (l)ower, or the (s)ame?   [Or (q)uit, or ? for a hint.]", &self.min_guess_options())
+    }
+
+    fn min_guess_options(&self) -> Vec<char> {
+        vec!['h', 'l', 's']
+    }
+
+    fn check_guess(&self, guess:&str) -> Result<bool, GuppiesError> {
+        let correct = match guess.to_lowercase().as_str() {
+            "h" => self.value_two > self.value_one,
+            "l" => self.value_two < self.value_one,
+            "s" => self.value_two == self.value_one,
+            _ => return Err(GuppiesError::InvalidGuess(guess.to_string()))
+        };
+        Ok(correct)
+    }
+
+    fn name(&self) -> &'static str {
+        "ProbabilityGuppies"
+    }
+
+    fn describe(&self) -> String {
+        format!("ProbabilityGuppies: two numbers are drawn from {} to {}.  After seeing the first one and the odds of each guess, guess whether the second is (h)igher, (l)ower, or the (s)ame.  A right guess wins your bet.",
+            PROBABILITY_MIN, PROBABILITY_MAX)
+    }
+
+    // The odds are already shown, so the hint explains where they come from
+    fn hint(&self) -> String {
+        let total = PROBABILITY_MAX - PROBABILITY_MIN + 1;
+        format!("Hint: each of the {} numbers is as likely as any other, so the odds of a guess are how many numbers it covers out of {}.  {} are higher than {} and {} are lower.",
+            total, total, PROBABILITY_MAX - self.value_one, self.value_one, self.value_one - PROBABILITY_MIN)
+    }
+
+    fn guess_chances(&self, first_value: i32) -> Vec<(char, f64)> {
+        let (higher, lower, same) = odds(first_value, PROBABILITY_MIN, PROBABILITY_MAX);
+        vec![('h', higher), ('l', lower), ('s', same)]
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Whether two chances are equal, give or take rounding
+    fn about(chance: f64, expected: f64) -> bool {
+        (chance - expected).abs() < 1e-9
+    }
+
+    #[test]
+    fn the_odds_at_each_end_of_the_range() {
+        let (higher, lower, same) = odds(1, 1, 10);
+        assert!(about(higher, 0.9) && about(lower, 0.0) && about(same, 0.1));
+        let (higher, lower, same) = odds(10, 1, 10);
+        assert!(about(higher, 0.0) && about(lower, 0.9) && about(same, 0.1));
+    }
+
+    #[test]
+    fn the_odds_add_up_over_a_huge_range() {
+        let (higher, lower, same) = odds(0, i32::MIN, i32::MAX);
+        assert!(about(higher + lower + same, 1.0));
+    }
+
+    #[test]
+    fn the_odds_are_shown_with_the_first_value() {
+        let variant = ProbabilityGuppies {value_one: 3, value_two: 1};
+        let mut input = std::io::empty();
+        let mut output = Vec::new();
+        let mut console = Console { input: &mut input, output: &mut output, quiet: false };
+        variant.tell_random(&mut console, DifferentValues::FirstGeneratedVal);
+        assert!(String::from_utf8(output).unwrap().contains("The odds: higher 70%, lower 20%, the same 10%."));
+    }
+}
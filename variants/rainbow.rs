@@ -0,0 +1,242 @@
+// rainbow.rs
+//
+// The RainbowGuppies variant, comparing how close two colors are to green.
+//
+
+use rand::{Rng, RngCore};  // for generating random numbers
+use crate::console::Console;
+use crate::error::GuppiesError;
+use super::{DifferentValues, GuppiesVariant, RoundContext, SAME_MULTIPLIER, likelihood};
+
+
+// The colors of the rainbow in order, shared by every RainbowGuppies method (and by
+// RainbowNumberGuppies, which counts colors nearer Red as warmer)
+pub(super) const RAINBOW_COLORS: [&str; 7] = ["Violet", "Indigo", "Blue", "Green", "Yellow", "Orange", "Red"];
+
+/// Picks a color from RAINBOW_COLORS with its own random index, so two calls
+/// are independent and only land on the same color by chance.
+///
+/// # Arguments
+///
+/// * `rng` - The random number generator to draw the index from.
+///
+fn random_rainbow_color(rng: &mut dyn RngCore) -> String {
+    let index = rng.gen_range(0..RAINBOW_COLORS.len());
+    RAINBOW_COLORS[index].to_string()
+}
+
+// RainbowGuppies variant of gameplay, which lists colors and asks the user if first color
+// is farther or closer to green when compared with the second color
+// tolerance is how many places apart on the rainbow two colors can be and still count as the same
+pub struct RainbowGuppies {
+    color_one: String,
+    color_two: String,
+    tolerance: usize,
+}
+
+impl RainbowGuppies {
+    /// Makes a RainbowGuppies game, where only the same color counts as the same.
+    pub fn new() -> RainbowGuppies {
+        RainbowGuppies::with_tolerance(0)
+    }
+
+    /// Makes a RainbowGuppies game where colors up to `tolerance` places apart on the rainbow
+    /// count as the same, e.g. with a tolerance of 1, Blue is the same as Indigo and Green.
+    ///
+    /// # Arguments
+    ///
+    /// * `tolerance` - How many places apart two colors can be and still be the same.
+    ///
+    pub fn with_tolerance(tolerance: usize) -> RainbowGuppies {
+        RainbowGuppies {color_one: "Indigo".to_string(), color_two: "Yellow".to_string(), tolerance}
+    }
+}
+
+impl Default for RainbowGuppies {
+    fn default() -> RainbowGuppies {
+        RainbowGuppies::new()
+    }
+}
+
+impl GuppiesVariant for RainbowGuppies {
+    fn generate_first(&mut self, rng: &mut dyn RngCore) {
+        // stores the first color inside the struct
+        self.color_one = random_rainbow_color(rng);
+    }
+
+    fn generate_second(&mut self, rng: &mut dyn RngCore) {
+        // draws a fresh index rather than reusing the first color's, so the two
+        // colors can differ; only the second color is replaced
+        self.color_two = random_rainbow_color(rng);
+    }
+
+    // Outputs the constructors to the user
+    fn tell_random(&self, console: &mut Console, value: DifferentValues) {
+        console.print(&format!("This is the {} color {}", value.ordinal(), self.display_value(value)))
+    }
+
+    fn display_value(&self, which: DifferentValues) -> String {
+        match which {
+            DifferentValues::FirstGeneratedVal => self.color_one.clone(),
+            DifferentValues::SecondGeneratedVal => self.color_two.clone(),
+        }
+    }
+
+    // The tolerance is a setting, not part of a game, so it is kept
+    fn reset(&mut self) {
+        *self = RainbowGuppies::with_tolerance(self.tolerance);
+    }
+
+    // gets the guesses from the user
+    fn get_guess(&self, console: &mut Console, round: &RoundContext) -> String {
+        self.prompt_guess(console, round, "Is the second color (c)loser, (f)arther, or the (s)ame when compared with green? [Or (q)uit, or ? for a hint.]", &self.min_guess_options())
+    }
+
+    fn min_guess_options(&self) -> Vec<char> {
+        vec!['c', 'f', 's']
+    }
+
+    fn guess_words(&self) -> Vec<(&'static str, char)> {
+        vec![("closer", 'c'), ("farther", 'f'), ("same", 's')]
+    }
+
+    // Resource: https://stackoverflow.com/questions/30558246/how-do-i-find-the-index-of-an-element-in-an-array-vector-or-slice
+    // Checks the validity of the guess
+    fn check_guess(&self, guess:&str) -> Result<bool, GuppiesError> {
+        let colors = RAINBOW_COLORS;
+       
+        // Used to obtian the index of green, color_one, and color_two
+        let index_one = colors.iter().position(|&r| r == self.color_two).unwrap();
+        let index_two = colors.iter().position(|&r| r == self.color_one).unwrap();
+        let index_green = colors.iter().position(|&r| r == "Green").unwrap();
+        
+        // Distance from green is measured with abs_diff, since the indices are usize and a
+        // plain subtraction would underflow (and panic) for colors before green
+        let distance_second = index_one.abs_diff(index_green);
+        let distance_first = index_two.abs_diff(index_green);
+
+        let correct = match guess.to_lowercase().as_str() {
+        // if the second color is fewer steps from green than the first then its closer
+        // if its more steps, then its farther  
+         "c" => distance_second < distance_first,
+         "f" => distance_second > distance_first,
+         // the same if the colors are no further apart on the rainbow than the tolerance
+         "s" => index_one.abs_diff(index_two) <= self.tolerance,
+         _ => return Err(GuppiesError::InvalidGuess(guess.to_string()))
+        };
+        Ok(correct)
+     }
+
+    fn name(&self) -> &'static str {
+        "RainbowGuppies"
+    }
+
+    fn describe(&self) -> String {
+        let same = match self.tolerance {
+            0 => "the (s)ame color".to_string(),
+            1 => "the (s)ame color or next to it on the rainbow".to_string(),
+            tolerance => format!("the (s)ame color or up to {} places from it on the rainbow", tolerance),
+        };
+        format!("RainbowGuppies: two colors are drawn from the rainbow (Violet, Indigo, Blue, Green, Yellow, Orange, Red).  After seeing the first one, guess whether the second is (c)loser to green, (f)arther from green, or {}.  A right guess wins twice your bet.", same)
+    }
+
+    // Counts how many colors are closer to and farther from green than the first color
+    fn hint(&self) -> String {
+        let index_green = RAINBOW_COLORS.iter().position(|&r| r == "Green").unwrap();
+        let index_first = RAINBOW_COLORS.iter().position(|&r| r == self.color_one).unwrap();
+        let distance_first = index_first.abs_diff(index_green);
+        let closer = (0..RAINBOW_COLORS.len()).filter(|index| index.abs_diff(index_green) < distance_first).count();
+        let farther = (0..RAINBOW_COLORS.len()).filter(|index| index.abs_diff(index_green) > distance_first).count();
+        let same = (0..RAINBOW_COLORS.len()).filter(|index| index.abs_diff(index_first) <= self.tolerance).count();
+        let total = RAINBOW_COLORS.len() as f64;
+        format!("Hint: closer is {}, farther is {}, the same is {}.",
+            likelihood(closer as f64 / total), likelihood(farther as f64 / total), likelihood(same as f64 / total))
+    }
+
+    // Comparing distances to green is the hardest to eyeball, so it pays double
+    fn payout_multiplier(&self) -> f64 {
+        2.0
+    }
+
+    // With a tolerance, colors near each other count as the same, so it's no longer rare
+    fn same_multiplier(&self) -> f64 {
+        if self.tolerance > 0 { self.payout_multiplier() } else { SAME_MULTIPLIER }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+    use super::*;
+
+    #[test]
+    fn the_two_colors_can_differ() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut variant = RainbowGuppies::new();
+        let mut differed = false;
+        for _ in 0..20 {
+            variant.generate_first(&mut rng);
+            variant.generate_second(&mut rng);
+            differed |= variant.color_one != variant.color_two;
+        }
+        assert!(differed);
+    }
+
+    fn with_colors(first: &str, second: &str) -> RainbowGuppies {
+        RainbowGuppies {color_one: first.to_string(), color_two: second.to_string(), tolerance: 0}
+    }
+
+    #[test]
+    fn violet_and_red_are_as_far_from_green() {
+        let variant = with_colors("Violet", "Red");
+        assert!(!variant.check_guess("c").unwrap());
+        assert!(!variant.check_guess("f").unwrap());
+        assert!(!variant.check_guess("s").unwrap());
+    }
+
+    #[test]
+    fn blue_and_yellow_are_as_far_from_green() {
+        let variant = with_colors("Blue", "Yellow");
+        assert!(!variant.check_guess("c").unwrap());
+        assert!(!variant.check_guess("f").unwrap());
+    }
+
+    #[test]
+    fn closer_and_farther_are_measured_either_side_of_green() {
+        assert!(with_colors("Violet", "Yellow").check_guess("c").unwrap());
+        assert!(with_colors("Yellow", "Indigo").check_guess("f").unwrap());
+    }
+
+    #[test]
+    fn the_same_color_is_the_same() {
+        let variant = with_colors("Blue", "Blue");
+        assert!(variant.check_guess("s").unwrap());
+        assert!(!variant.check_guess("c").unwrap());
+    }
+
+    #[test]
+    fn the_values_are_shown_as_color_names() {
+        let mut variant = RainbowGuppies::new();
+        let mut rng = StdRng::seed_from_u64(11);
+        variant.generate_first(&mut rng);
+        variant.generate_second(&mut rng);
+        for which in [DifferentValues::FirstGeneratedVal, DifferentValues::SecondGeneratedVal] {
+            assert!(RAINBOW_COLORS.contains(&variant.display_value(which).as_str()));
+        }
+    }
+
+    #[test]
+    fn a_tolerance_of_1_counts_neighbours_as_the_same() {
+        let variant = RainbowGuppies {tolerance: 1, ..with_colors("Blue", "Indigo")};
+        assert!(variant.check_guess("s").unwrap());
+        assert!(!RainbowGuppies {tolerance: 1, ..with_colors("Blue", "Violet")}.check_guess("s").unwrap());
+    }
+
+    #[test]
+    fn a_tolerance_of_0_only_counts_the_same_color() {
+        assert!(!with_colors("Blue", "Indigo").check_guess("s").unwrap());
+        assert!(with_colors("Indigo", "Indigo").check_guess("s").unwrap());
+    }
+}
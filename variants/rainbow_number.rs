@@ -0,0 +1,164 @@
+// rainbow_number.rs
+//
+// The RainbowNumberGuppies variant, asking about a color and a number at once.
+//
+
+use rand::{Rng, RngCore};  // for generating random numbers
+use crate::console::Console;
+use crate::error::GuppiesError;
+use super::rainbow::RAINBOW_COLORS;
+use super::{DifferentValues, GuppiesVariant, MAX_ODDS_MULTIPLIER, RoundContext, describe_payouts, likelihood, pay_by_odds};
+
+
+// The numbers are drawn from 1 to 10
+const NUMBER_MIN: i32 = 1;
+const NUMBER_MAX: i32 = 10;
+
+
+// RainbowNumberGuppies draws a color and a number together, twice, and asks a compound
+// question: is the second draw both a warmer color (nearer Red on the rainbow) and a higher
+// number than the first?  Each draw is a pair of the color's index in RAINBOW_COLORS and the
+// number, so a warmer color is simply a bigger index
+pub struct RainbowNumberGuppies {
+    first_draw: (usize, i32),
+    second_draw: (usize, i32),
+}
+
+impl RainbowNumberGuppies {
+    /// Makes a RainbowNumberGuppies game, with numbers from 1 to 10.
+    pub fn new() -> RainbowNumberGuppies {
+        RainbowNumberGuppies {first_draw: (0, NUMBER_MIN), second_draw: (0, NUMBER_MIN)}
+    }
+}
+
+impl Default for RainbowNumberGuppies {
+    fn default() -> RainbowNumberGuppies {
+        RainbowNumberGuppies::new()
+    }
+}
+
+// Draws a color and a number, each on its own
+fn random_draw(rng: &mut dyn RngCore) -> (usize, i32) {
+    (rng.gen_range(0..RAINBOW_COLORS.len()), rng.gen_range(NUMBER_MIN..NUMBER_MAX + 1))
+}
+
+// The chances of a warmer color and of a higher number after the first draw
+fn warmer_higher_chances(first: (usize, i32)) -> (f64, f64) {
+    let warmer = (RAINBOW_COLORS.len() - 1 - first.0) as f64 / RAINBOW_COLORS.len() as f64;
+    let higher = (NUMBER_MAX - first.1) as f64 / (NUMBER_MAX - NUMBER_MIN + 1) as f64;
+    (warmer, higher)
+}
+
+// The chance of each guess after the first draw.  The color and the number are drawn
+// separately, so the chance of both is the two chances multiplied
+fn answer_chances(first: (usize, i32)) -> Vec<(char, f64)> {
+    let (warmer, higher) = warmer_higher_chances(first);
+    vec![('y', warmer * higher), ('n', 1.0 - warmer * higher)]
+}
+
+impl GuppiesVariant for RainbowNumberGuppies {
+    fn generate_first(&mut self, rng: &mut dyn RngCore) {
+        self.first_draw = random_draw(rng);
+    }
+
+    fn generate_second(&mut self, rng: &mut dyn RngCore) {
+        self.second_draw = random_draw(rng);
+    }
+
+    fn tell_random(&self, console: &mut Console, value: DifferentValues) {
+        let shown = format!("This is the {} draw {}", value.ordinal(), self.display_value(value));
+        match value {
+            // Each guess is paid by its odds, which depend on the first draw, so they're shown with it
+            DifferentValues::FirstGeneratedVal => console.print(&format!("{}\n{}", shown, describe_payouts(&answer_chances(self.first_draw)))),
+            DifferentValues::SecondGeneratedVal => console.print(&shown),
+        }
+    }
+
+    fn display_value(&self, which: DifferentValues) -> String {
+        let (color, number) = match which {
+            DifferentValues::FirstGeneratedVal => self.first_draw,
+            DifferentValues::SecondGeneratedVal => self.second_draw,
+        };
+        format!("{} {}", RAINBOW_COLORS[color], number)
+    }
+
+    fn reset(&mut self) {
+        *self = RainbowNumberGuppies::new();
+    }
+
+    fn get_guess(&self, console: &mut Console, round: &RoundContext) -> String {
+        self.prompt_guess(console, round, "Will the second draw be BOTH a warmer color (nearer Red) AND a higher number?  (y)es if both, (n)o if either one isn't.   [Or (q)uit, or ? for a hint.]", &self.min_guess_options())
+    }
+
+    fn min_guess_options(&self) -> Vec<char> {
+        vec!['y', 'n']
+    }
+
+    fn guess_words(&self) -> Vec<(&'static str, char)> {
+        vec![("yes", 'y'), ("no", 'n')]
+    }
+
+    // A tie on either the color or the number isn't warmer or higher, so it makes the answer no
+    fn check_guess(&self, guess:&str) -> Result<bool, GuppiesError> {
+        let warmer = self.second_draw.0 > self.first_draw.0;
+        let higher = self.second_draw.1 > self.first_draw.1;
+        let correct = match guess.to_lowercase().as_str() {
+            "y" => warmer && higher,
+            "n" => !(warmer && higher),
+            _ => return Err(GuppiesError::InvalidGuess(guess.to_string()))
+        };
+        Ok(correct)
+    }
+
+    fn name(&self) -> &'static str {
+        "RainbowNumberGuppies"
+    }
+
+    fn describe(&self) -> String {
+        format!("RainbowNumberGuppies: a color from the rainbow (Violet, Indigo, Blue, Green, Yellow, Orange, Red) and a number from {} to {} are drawn together, and then another pair.  After seeing the first pair, guess (y)es if the second pair will be both a warmer color (nearer Red) and a higher number, or (n)o if it won't be both.  No is right far more often, so each guess is paid by its odds, which are shown with the first pair.",
+            NUMBER_MIN, NUMBER_MAX)
+    }
+
+    fn hint(&self) -> String {
+        let (warmer, higher) = warmer_higher_chances(self.first_draw);
+        format!("Hint: a warmer color is {}, a higher number is {}, so both is {}.",
+            likelihood(warmer), likelihood(higher), likelihood(warmer * higher))
+    }
+
+    // No wins about 80% of the time, so paying both guesses the same would make always
+    // guessing it a winning strategy.  Each guess is paid by its odds instead
+    fn guess_multiplier(&self, guess: &str) -> f64 {
+        pay_by_odds(&answer_chances(self.first_draw), guess)
+    }
+
+    fn max_multiplier(&self) -> f64 {
+        MAX_ODDS_MULTIPLIER
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Blue and 5 first, then the color at index second_color with second_number
+    fn second(second_color: usize, second_number: i32) -> RainbowNumberGuppies {
+        RainbowNumberGuppies {first_draw: (2, 5), second_draw: (second_color, second_number)}
+    }
+
+    #[test]
+    fn yes_needs_a_warmer_color_and_a_higher_number() {
+        // warmer and higher, warmer and lower, cooler and higher, cooler and lower
+        let cases = [(second(6, 9), true), (second(6, 1), false), (second(0, 9), false), (second(0, 1), false)];
+        for (variant, both) in cases {
+            assert_eq!(variant.check_guess("y").unwrap(), both);
+            assert_eq!(variant.check_guess("n").unwrap(), !both);
+        }
+    }
+
+    #[test]
+    fn the_same_color_or_number_isnt_warmer_or_higher() {
+        assert!(!second(2, 9).check_guess("y").unwrap());
+        assert!(!second(6, 5).check_guess("y").unwrap());
+    }
+}
@@ -0,0 +1,139 @@
+// roman.rs
+//
+// The RomanNumeralGuppies variant, the higher/lower/same game with Roman numerals.
+//
+
+use rand::{Rng, RngCore};  // for generating random numbers
+use crate::console::Console;
+use crate::error::GuppiesError;
+use super::{DifferentValues, GuppiesVariant, RoundContext, likelihood};
+
+
+// Each Roman numeral symbol (including the subtractive pairs like IV) with its value, largest first
+const ROMAN_SYMBOLS: [(i32, &str); 9] = [
+    (100, "C"), (90, "XC"), (50, "L"), (40, "XL"), (10, "X"), (9, "IX"), (5, "V"), (4, "IV"), (1, "I"),
+];
+
+
+/// Writes a number from 1 to 100 as a Roman numeral, e.g. 49 is XLIX.
+///
+/// # Arguments
+///
+/// * `n` - The number to write, from 1 to 100.  Anything below 1 comes out as an empty string.
+///
+fn to_roman(n: i32) -> String {
+    let mut remaining = n;
+    let mut roman = String::new();
+    for (value, symbol) in ROMAN_SYMBOLS {
+        while remaining >= value {
+            roman.push_str(symbol);
+            remaining -= value;
+        }
+    }
+    roman
+}
+
+
+// RomanNumeralGuppies draws two numbers from 1 to 100 like the other number games, but only
+// ever shows them as Roman numerals.  The guess is still about the numbers themselves.
+pub struct RomanNumeralGuppies {
+    num_one: i32,
+    num_two: i32,
+}
+
+impl RomanNumeralGuppies {
+    /// Makes a RomanNumeralGuppies game, with values from 1 to 100 inclusive.
+    pub fn new() -> RomanNumeralGuppies {
+        RomanNumeralGuppies {num_one: 1, num_two: 1}
+    }
+}
+
+impl Default for RomanNumeralGuppies {
+    fn default() -> RomanNumeralGuppies {
+        RomanNumeralGuppies::new()
+    }
+}
+
+// This struct implements GuppiesVariant
+impl GuppiesVariant for RomanNumeralGuppies {
+    fn generate_first(&mut self, rng: &mut dyn RngCore) {
+        self.num_one = rng.gen_range(1..101);
+    }
+
+    fn generate_second(&mut self, rng: &mut dyn RngCore) {
+        // Only the second number is rolled, so the first one stays what the user saw
+        self.num_two = rng.gen_range(1..101);
+    }
+
+    fn tell_random(&self, console: &mut Console, value: DifferentValues) {
+        console.print(&format!("This is the {} numeral {}", value.ordinal(), self.display_value(value)))
+    }
+
+    fn display_value(&self, which: DifferentValues) -> String {
+        match which {
+            DifferentValues::FirstGeneratedVal => to_roman(self.num_one),
+            DifferentValues::SecondGeneratedVal => to_roman(self.num_two),
+        }
+    }
+
+    fn reset(&mut self) {
+        *self = RomanNumeralGuppies::new();
+    }
+
+    // Gets the guess from the user and makes sure its not invalid
+    fn get_guess(&self, console: &mut Console, round: &RoundContext) -> String {
+        self.prompt_guess(console, round, "Is the second numeral (h)igher, (l)ower, or the (s)ame?   [Or (q)uit, or ? for a hint.]", &self.min_guess_options())
+    }
+
+    fn min_guess_options(&self) -> Vec<char> {
+        vec!['h', 'l', 's']
+    }
+
+    // Checks the users guesses against the numbers behind the numerals
+    fn check_guess(&self, guess:&str) -> Result<bool, GuppiesError> {
+        let correct = match guess.to_lowercase().as_str() {
+            "h" => self.num_two > self.num_one,
+            "l" => self.num_two < self.num_one,
+            "s" => self.num_two == self.num_one,
+            _ => return Err(GuppiesError::InvalidGuess(guess.to_string()))
+        };
+        Ok(correct)
+    }
+
+    fn name(&self) -> &'static str {
+        "RomanNumeralGuppies"
+    }
+
+    fn describe(&self) -> String {
+        "RomanNumeralGuppies: two numbers are drawn from I to C (1 to 100), and shown only as Roman numerals.  After seeing the first one, guess whether the second is (h)igher, (l)ower, or the (s)ame.  A right guess wins your bet.".to_string()
+    }
+
+    // Counts the numbers above and below the first one
+    fn hint(&self) -> String {
+        let higher = (100 - self.num_one) as f64 / 100.0;
+        let lower = (self.num_one - 1) as f64 / 100.0;
+        format!("Hint: higher is {}, lower is {}, the same is {}.", likelihood(higher), likelihood(lower), likelihood(0.01))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn to_roman_writes_numerals_with_subtraction() {
+        assert_eq!(to_roman(4), "IV");
+        assert_eq!(to_roman(49), "XLIX");
+        assert_eq!(to_roman(100), "C");
+        assert_eq!(to_roman(88), "LXXXVIII");
+    }
+
+    #[test]
+    fn the_numbers_are_shown_as_numerals_and_compared_as_numbers() {
+        let variant = RomanNumeralGuppies {num_one: 49, num_two: 51};
+        assert_eq!(variant.display_value(DifferentValues::FirstGeneratedVal), "XLIX");
+        assert_eq!(variant.display_value(DifferentValues::SecondGeneratedVal), "LI");
+        assert!(variant.check_guess("h").unwrap());
+    }
+}
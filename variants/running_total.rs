@@ -0,0 +1,157 @@
+// running_total.rs
+//
+// The RunningTotalGuppies variant, keeping count of a hidden total across rounds.
+//
+
+use rand::{Rng, RngCore};  // for generating random numbers
+use crate::console::Console;
+use crate::error::GuppiesError;
+use super::{DifferentValues, GuppiesVariant, RoundContext, likelihood};
+
+
+// Every round adds a number from -10 to 10 to the total, and the threshold is drawn from the
+// same range so the total keeps wandering back and forth across it
+const NUMBER_MIN: i32 = -10;
+const NUMBER_MAX: i32 = 10;
+
+
+// RunningTotalGuppies remembers across rounds: the threshold is drawn once at the start of a
+// game, and every round's number is added to a running total the user never sees.  The
+// guess is where the total ends up against the threshold once the round's number is added
+// numbers_drawn is how many numbers have gone into the total, 0 until the first round
+pub struct RunningTotalGuppies {
+    running_total: i32,
+    threshold: i32,
+    number: i32,
+    numbers_drawn: u32,
+}
+
+impl RunningTotalGuppies {
+    /// Makes a RunningTotalGuppies game, with the total starting at 0.
+    pub fn new() -> RunningTotalGuppies {
+        RunningTotalGuppies {running_total: 0, threshold: 0, number: 0, numbers_drawn: 0}
+    }
+}
+
+impl Default for RunningTotalGuppies {
+    fn default() -> RunningTotalGuppies {
+        RunningTotalGuppies::new()
+    }
+}
+
+impl GuppiesVariant for RunningTotalGuppies {
+    // The threshold is only drawn for the first round, and stays for the rest of the game
+    fn generate_first(&mut self, rng: &mut dyn RngCore) {
+        if self.numbers_drawn == 0 {
+            self.threshold = rng.gen_range(NUMBER_MIN..NUMBER_MAX + 1);
+        }
+    }
+
+    fn generate_second(&mut self, rng: &mut dyn RngCore) {
+        self.number = rng.gen_range(NUMBER_MIN..NUMBER_MAX + 1);
+        self.running_total += self.number;
+        self.numbers_drawn += 1;
+    }
+
+    // The total itself is never told, only the threshold and each number added to it
+    fn tell_random(&self, console: &mut Console, value: DifferentValues) {
+        match value {
+            DifferentValues::FirstGeneratedVal if self.numbers_drawn == 0 => {
+                console.print(&format!("The threshold is {}, and the hidden total starts at 0.", self.display_value(value)))
+            }
+            DifferentValues::FirstGeneratedVal => console.print(&format!("The threshold is still {}.", self.display_value(value))),
+            DifferentValues::SecondGeneratedVal => console.print(&format!("The number added to the total was {}", self.display_value(value))),
+        }
+    }
+
+    fn display_value(&self, which: DifferentValues) -> String {
+        match which {
+            DifferentValues::FirstGeneratedVal => self.threshold.to_string(),
+            DifferentValues::SecondGeneratedVal => format!("{:+}", self.number),
+        }
+    }
+
+    fn reset(&mut self) {
+        *self = RunningTotalGuppies::new();
+    }
+
+    fn get_guess(&self, console: &mut Console, round: &RoundContext) -> String {
+        self.prompt_guess(console, round, "Once the next number is added, will the total be (h)igher, (l)ower, or the (s)ame as the threshold?   [Or (q)uit, or ? for a hint.]", &self.min_guess_options())
+    }
+
+    fn min_guess_options(&self) -> Vec<char> {
+        vec!['h', 'l', 's']
+    }
+
+    // The total already has this round's number in it by the time the guess is checked
+    fn check_guess(&self, guess:&str) -> Result<bool, GuppiesError> {
+        let correct = match guess.to_lowercase().as_str() {
+            "h" => self.running_total > self.threshold,
+            "l" => self.running_total < self.threshold,
+            "s" => self.running_total == self.threshold,
+            _ => return Err(GuppiesError::InvalidGuess(guess.to_string()))
+        };
+        Ok(correct)
+    }
+
+    fn name(&self) -> &'static str {
+        "RunningTotalGuppies"
+    }
+
+    fn describe(&self) -> String {
+        format!("RunningTotalGuppies: a threshold from {} to {} is shown at the start of the game, and a hidden total starts at 0.  Every round a number from {} to {} is added to the total, and before it is shown you guess whether the total will then be (h)igher, (l)ower, or the (s)ame as the threshold.  The total is never shown, so keep count!  A right guess wins one and a half times your bet.",
+            NUMBER_MIN, NUMBER_MAX, NUMBER_MIN, NUMBER_MAX)
+    }
+
+    // Keeping count is the game, so the hint only goes on what the user has seen: how many
+    // numbers are in the total, and how the next one could move it.  Never the total itself
+    fn hint(&self) -> String {
+        let numbers = NUMBER_MIN..NUMBER_MAX + 1;
+        let total = numbers.len() as f64;
+        let up = numbers.clone().filter(|&number| number > 0).count() as f64 / total;
+        let down = numbers.clone().filter(|&number| number < 0).count() as f64 / total;
+        let none = numbers.filter(|&number| number == 0).count() as f64 / total;
+        format!("Hint: numbers added to the total so far: {}.  The next number raising it is {}, lowering it is {}, and leaving it alone is {}.",
+            self.numbers_drawn, likelihood(up), likelihood(down), likelihood(none))
+    }
+
+    // Keeping count in your head is harder than seeing the numbers, so it pays more
+    fn payout_multiplier(&self) -> f64 {
+        1.5
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+    use super::*;
+
+    #[test]
+    fn guesses_are_checked_against_the_whole_total() {
+        let mut variant = RunningTotalGuppies::new();
+        let mut rng = StdRng::seed_from_u64(87);
+        let mut threshold = None;
+        let mut total = 0;
+        for _ in 0..6 {
+            variant.generate_first(&mut rng);
+            let threshold = *threshold.get_or_insert(variant.threshold);
+            variant.generate_second(&mut rng);
+            total += variant.number;
+            assert_eq!(variant.threshold, threshold, "the threshold stays for the whole game");
+            assert_eq!(variant.running_total, total);
+            assert_eq!(variant.check_guess("h").unwrap(), total > threshold);
+            assert_eq!(variant.check_guess("l").unwrap(), total < threshold);
+        }
+        assert_eq!(variant.numbers_drawn, 6);
+    }
+
+    #[test]
+    fn a_small_number_can_still_leave_the_total_higher() {
+        // 8 and then 6 have been added, so -3 still leaves the total of 11 above a threshold of 5
+        let variant = RunningTotalGuppies {running_total: 11, threshold: 5, number: -3, numbers_drawn: 3};
+        assert!(variant.check_guess("h").unwrap());
+        assert!(!variant.check_guess("l").unwrap());
+    }
+}
@@ -0,0 +1,174 @@
+// square_root.rs
+//
+// The SquareRootGuppies variant, comparing a number to the perfect square below the first one.
+//
+
+use rand::{Rng, RngCore};  // for generating random numbers
+use crate::console::Console;
+use crate::error::GuppiesError;
+use super::{DifferentValues, GuppiesVariant, RoundContext, likelihood};
+
+
+// Both numbers are drawn from 1 to 100
+const SQUARE_MIN: i32 = 1;
+const SQUARE_MAX: i32 = 100;
+
+
+/// Checks whether n is a perfect square, like 16 (4 x 4).  Nothing below 0 is.
+///
+/// # Arguments
+///
+/// * `n` - The number to check.
+///
+fn is_perfect_square(n: i32) -> bool {
+    n >= 0 && nearest_square_below(n) == n
+}
+
+/// Finds the biggest perfect square that isn't bigger than n, e.g. 16 for 20, and 16 for 16.
+///
+/// # Arguments
+///
+/// * `n` - The number to find the perfect square below.
+///
+/// # Returns
+///
+/// * i32: the perfect square, which is 0 for anything below 1.
+///
+fn nearest_square_below(n: i32) -> i32 {
+    let mut root = 0;
+    while (root + 1) * (root + 1) <= n {
+        root += 1;
+    }
+    root * root
+}
+
+
+// SquareRootGuppies draws a number from 1 to 100 and shows it along with the perfect square
+// below it.  The user guesses whether the second number is a perfect square itself, or else
+// (h)igher or (l)ower than that perfect square
+pub struct SquareRootGuppies {
+    num_one: i32,
+    num_two: i32,
+}
+
+impl SquareRootGuppies {
+    /// Makes a SquareRootGuppies game.
+    pub fn new() -> SquareRootGuppies {
+        SquareRootGuppies {num_one: SQUARE_MIN, num_two: SQUARE_MIN}
+    }
+}
+
+impl Default for SquareRootGuppies {
+    fn default() -> SquareRootGuppies {
+        SquareRootGuppies::new()
+    }
+}
+
+// This struct implements GuppiesVariant
+impl GuppiesVariant for SquareRootGuppies {
+    fn generate_first(&mut self, rng: &mut dyn RngCore) {
+        self.num_one = rng.gen_range(SQUARE_MIN..SQUARE_MAX + 1);
+    }
+
+    fn generate_second(&mut self, rng: &mut dyn RngCore) {
+        // Only the second number is rolled, so the first one stays what the user saw
+        self.num_two = rng.gen_range(SQUARE_MIN..SQUARE_MAX + 1);
+    }
+
+    fn tell_random(&self, console: &mut Console, value: DifferentValues) {
+        let shown = format!("This is the {} number {}", value.ordinal(), self.display_value(value));
+        match value {
+            DifferentValues::FirstGeneratedVal => {
+                console.print(&format!("{} (the perfect square below it is {})", shown, nearest_square_below(self.num_one)))
+            }
+            DifferentValues::SecondGeneratedVal => console.print(&shown),
+        }
+    }
+
+    fn display_value(&self, which: DifferentValues) -> String {
+        match which {
+            DifferentValues::FirstGeneratedVal => self.num_one.to_string(),
+            DifferentValues::SecondGeneratedVal => self.num_two.to_string(),
+        }
+    }
+
+    fn reset(&mut self) {
+        *self = SquareRootGuppies::new();
+    }
+
+    // Gets the guess from the user and makes sure its not invalid
+    fn get_guess(&self, console: &mut Console, round: &RoundContext) -> String {
+        self.prompt_guess(console, round, "Is the second number (h)igher or (l)ower than the perfect square, or a perfect (s)quare itself?   [Or (q)uit, or ? for a hint.]", &self.min_guess_options())
+    }
+
+    fn min_guess_options(&self) -> Vec<char> {
+        vec!['h', 'l', 's']
+    }
+
+    fn guess_words(&self) -> Vec<(&'static str, char)> {
+        vec![("higher", 'h'), ("lower", 'l'), ("square", 's')]
+    }
+
+    // A perfect square second number only counts for "s", so every number has exactly one right guess
+    fn check_guess(&self, guess:&str) -> Result<bool, GuppiesError> {
+        let square = nearest_square_below(self.num_one);
+        let perfect = is_perfect_square(self.num_two);
+        let correct = match guess.to_lowercase().as_str() {
+            "h" => !perfect && self.num_two > square,
+            "l" => !perfect && self.num_two < square,
+            "s" => perfect,
+            _ => return Err(GuppiesError::InvalidGuess(guess.to_string()))
+        };
+        Ok(correct)
+    }
+
+    fn name(&self) -> &'static str {
+        "SquareRootGuppies"
+    }
+
+    fn describe(&self) -> String {
+        format!("SquareRootGuppies: two numbers are drawn from {} to {}.  You're shown the first one and the biggest perfect square (1, 4, 9, 16, ...) that isn't above it.  Guess whether the second number is a perfect (s)quare itself, or else (h)igher or (l)ower than that perfect square.  A right guess wins your bet.", SQUARE_MIN, SQUARE_MAX)
+    }
+
+    // Counts how many of the possible second numbers each guess covers
+    fn hint(&self) -> String {
+        let square = nearest_square_below(self.num_one);
+        let total = (SQUARE_MAX - SQUARE_MIN + 1) as f64;
+        let chance = |right: &dyn Fn(i32) -> bool| (SQUARE_MIN..=SQUARE_MAX).filter(|&n| right(n)).count() as f64 / total;
+        let higher = chance(&|n| !is_perfect_square(n) && n > square);
+        let lower = chance(&|n| !is_perfect_square(n) && n < square);
+        let perfect = chance(&is_perfect_square);
+        format!("Hint: higher is {}, lower is {}, a perfect square is {}.", likelihood(higher), likelihood(lower), likelihood(perfect))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn perfect_squares() {
+        assert!(is_perfect_square(16));
+        assert!(!is_perfect_square(15));
+        assert!(is_perfect_square(0));
+        assert!(!is_perfect_square(-4));
+    }
+
+    #[test]
+    fn the_nearest_square_below() {
+        assert_eq!(nearest_square_below(20), 16);
+        assert_eq!(nearest_square_below(16), 16);
+        assert_eq!(nearest_square_below(0), 0);
+    }
+
+    #[test]
+    fn a_perfect_square_is_only_right_for_s() {
+        // 20 shows 16, so 17 is higher and 12 is lower, but 9 is a perfect square
+        let variant = |num_two| SquareRootGuppies {num_one: 20, num_two};
+        assert!(variant(17).check_guess("h").unwrap());
+        assert!(variant(12).check_guess("l").unwrap());
+        assert!(variant(9).check_guess("s").unwrap());
+        assert!(!variant(9).check_guess("l").unwrap());
+    }
+}
@@ -0,0 +1,149 @@
+// sum.rs
+//
+// The SumGuppies variant, comparing the digit sums of two numbers.
+//
+
+use rand::{Rng, RngCore};  // for generating random numbers
+use crate::console::Console;
+use crate::error::GuppiesError;
+use super::{DifferentValues, GuppiesVariant, RoundContext, likelihood};
+
+
+// Both numbers have 2 or 3 digits
+const SUM_MIN: i32 = 10;
+const SUM_MAX: i32 = 999;
+
+
+/// Adds up the digits of a number, e.g. 472 is 4 + 7 + 2 = 13.  The sign is ignored.
+///
+/// # Arguments
+///
+/// * `n` - The number whose digits are added up.
+///
+fn digit_sum(n: i32) -> i32 {
+    let mut n = n.unsigned_abs();
+    let mut sum = 0;
+    while n > 0 {
+        sum += n % 10;
+        n /= 10;
+    }
+    sum as i32
+}
+
+
+// SumGuppies draws two numbers from 10 to 999 and asks whether the second number's digit
+// sum is higher, lower, or the same as the first number's
+pub struct SumGuppies {
+    num_one: i32,
+    num_two: i32,
+}
+
+impl SumGuppies {
+    /// Makes a SumGuppies game.
+    pub fn new() -> SumGuppies {
+        SumGuppies {num_one: SUM_MIN, num_two: SUM_MIN}
+    }
+}
+
+impl Default for SumGuppies {
+    fn default() -> SumGuppies {
+        SumGuppies::new()
+    }
+}
+
+// This struct implements GuppiesVariant
+impl GuppiesVariant for SumGuppies {
+    fn generate_first(&mut self, rng: &mut dyn RngCore) {
+        self.num_one = rng.gen_range(SUM_MIN..SUM_MAX + 1);
+    }
+
+    fn generate_second(&mut self, rng: &mut dyn RngCore) {
+        // Only the second number is rolled, so the first one stays what the user saw
+        self.num_two = rng.gen_range(SUM_MIN..SUM_MAX + 1);
+    }
+
+    // Working out the first digit sum is part of the game, so both sums are only shown
+    // along with the second number
+    fn tell_random(&self, console: &mut Console, value: DifferentValues) {
+        let shown = format!("This is the {} number {}", value.ordinal(), self.display_value(value));
+        match value {
+            DifferentValues::FirstGeneratedVal => console.print(&shown),
+            DifferentValues::SecondGeneratedVal => {
+                console.print(&format!("{} (digit sums: {} and {})", shown, digit_sum(self.num_one), digit_sum(self.num_two)))
+            }
+        }
+    }
+
+    fn display_value(&self, which: DifferentValues) -> String {
+        match which {
+            DifferentValues::FirstGeneratedVal => self.num_one.to_string(),
+            DifferentValues::SecondGeneratedVal => self.num_two.to_string(),
+        }
+    }
+
+    fn reset(&mut self) {
+        *self = SumGuppies::new();
+    }
+
+    // Gets the guess from the user and makes sure its not invalid
+    fn get_guess(&self, console: &mut Console, round: &RoundContext) -> String {
+        self.prompt_guess(console, round, "Is the second number's digit sum (h)igher, (l)ower, or the (s)ame?   [Or (q)uit, or ? for a hint.]", &self.min_guess_options())
+    }
+
+    fn min_guess_options(&self) -> Vec<char> {
+        vec!['h', 'l', 's']
+    }
+
+    // Checks the users guesses against the digit sums, not the numbers themselves
+    fn check_guess(&self, guess:&str) -> Result<bool, GuppiesError> {
+        let first = digit_sum(self.num_one);
+        let second = digit_sum(self.num_two);
+        let correct = match guess.to_lowercase().as_str() {
+            "h" => second > first,
+            "l" => second < first,
+            "s" => second == first,
+            _ => return Err(GuppiesError::InvalidGuess(guess.to_string()))
+        };
+        Ok(correct)
+    }
+
+    fn name(&self) -> &'static str {
+        "SumGuppies"
+    }
+
+    fn describe(&self) -> String {
+        format!("SumGuppies: two numbers are drawn from {} to {}.  After seeing the first one, guess whether the second number's digit sum (its digits added up, like 4 + 7 + 2 for 472) is (h)igher, (l)ower, or the (s)ame as the first's.  A right guess wins your bet.", SUM_MIN, SUM_MAX)
+    }
+
+    // Counts how many of the possible second numbers have a higher or lower digit sum
+    fn hint(&self) -> String {
+        let first = digit_sum(self.num_one);
+        let total = (SUM_MAX - SUM_MIN + 1) as f64;
+        let higher = (SUM_MIN..=SUM_MAX).filter(|&n| digit_sum(n) > first).count() as f64 / total;
+        let lower = (SUM_MIN..=SUM_MAX).filter(|&n| digit_sum(n) < first).count() as f64 / total;
+        format!("Hint: higher is {}, lower is {}, the same is {}.", likelihood(higher), likelihood(lower), likelihood(1.0 - higher - lower))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn digit_sum_adds_up_the_digits() {
+        assert_eq!(digit_sum(99), 18);
+        assert_eq!(digit_sum(100), 1);
+        assert_eq!(digit_sum(-47), 11);
+    }
+
+    #[test]
+    fn the_digit_sums_are_compared_not_the_numbers() {
+        // 19 adds up to 10 and 100 to 1, though 100 is the bigger number
+        let variant = SumGuppies {num_one: 19, num_two: 100};
+        assert!(variant.check_guess("l").unwrap());
+        assert!(!variant.check_guess("h").unwrap());
+        // 123 and 600 both add up to 6
+        assert!(SumGuppies {num_one: 123, num_two: 600}.check_guess("s").unwrap());
+    }
+}